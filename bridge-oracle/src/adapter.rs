@@ -0,0 +1,65 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, vec, Address, Env, IntoVal, Val, Vec};
+use crate::{price, reflector, storage};
+
+/// The interface an oracle contract follows. Both quote every asset with the decimals of the
+/// oracle, which are rescaled to the decimals of the bridge oracle
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum Adapter {
+    /// The SEP-40 oracle interface
+    Sep40,
+    /// The Reflector oracle interface
+    Reflector,
+}
+
+/// Check if an oracle follows the interface of an adapter
+/// # Arguments
+/// * `oracle` - The oracle to check
+/// * `adapter` - The adapter of the oracle
+pub fn is_valid(env: &Env, oracle: &Address, adapter: Adapter) -> bool {
+    match adapter {
+        Adapter::Sep40 => true,
+        Adapter::Reflector => reflector::resolution(env, oracle).is_some(),
+    }
+}
+
+/// Fetch the last price of an asset from an oracle, in the decimals of the oracle
+/// # Arguments
+/// * `oracle` - The oracle quoting the asset
+/// * `asset` - The asset to fetch the price for
+pub fn lastprice(env: &Env, oracle: &Address, asset: &Asset) -> Option<PriceData> {
+    match storage::get_adapter(env, oracle) {
+        Adapter::Sep40 => {
+            let args: Vec<Val> = vec![env, asset.into_val(env)];
+            price::try_invoke::<Option<PriceData>>(env, oracle, "lastprice", args).flatten()
+        }
+        Adapter::Reflector => reflector::lastprice(env, oracle, asset),
+    }
+}
+
+/// Fetch the price of an asset at a timestamp from an oracle, in the decimals of the oracle
+/// # Arguments
+/// * `oracle` - The oracle quoting the asset
+/// * `asset` - The asset to fetch the price for
+/// * `timestamp` - The timestamp to fetch the price at
+pub fn price(env: &Env, oracle: &Address, asset: &Asset, timestamp: u64) -> Option<PriceData> {
+    match storage::get_adapter(env, oracle) {
+        Adapter::Sep40 => {
+            let args: Vec<Val> = vec![env, asset.into_val(env), timestamp.into_val(env)];
+            price::try_invoke::<Option<PriceData>>(env, oracle, "price", args).flatten()
+        }
+        Adapter::Reflector => reflector::price(env, oracle, asset, timestamp),
+    }
+}
+
+/// Fetch the last prices of an asset from an oracle, in the decimals of the oracle
+/// # Arguments
+/// * `oracle` - The oracle quoting the asset
+/// * `asset` - The asset to fetch the prices for
+/// * `records` - The number of prices to fetch
+pub fn prices(env: &Env, oracle: &Address, asset: &Asset, records: u32) -> Option<Vec<PriceData>> {
+    // both interfaces share the signature of prices
+    let args: Vec<Val> = vec![env, asset.into_val(env), records.into_val(env)];
+    price::try_invoke::<Option<Vec<PriceData>>>(env, oracle, "prices", args).flatten()
+}
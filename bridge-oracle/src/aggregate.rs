@@ -0,0 +1,125 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+use crate::constants::SCALAR_7;
+use crate::{adapter, price};
+
+/// The price sources of an asset, aggregated by their median
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceSources {
+    /// The oracles quoting the asset
+    pub sources: Vec<Address>,
+    /// The number of sources that must agree on a price
+    pub quorum: u32,
+    /// The max deviation of a source from the median, with 7 decimals
+    pub tolerance: i128,
+    /// The max age of a source price in seconds, or 0 for no max age
+    pub max_age: u64,
+}
+
+/// A price aggregated from multiple sources
+#[derive(Clone)]
+#[contracttype]
+pub struct AggregatePrice {
+    /// The median price of the sources that took part
+    pub price: PriceData,
+    /// The sources that took part in the price
+    pub sources: Vec<Address>,
+    /// The spread between the highest and lowest price of the sources that took part, relative
+    /// to the median with 7 decimals
+    pub spread: i128,
+}
+
+/// Aggregate the last prices of the sources of an asset
+/// # Arguments
+/// * `config` - The price sources of the asset
+/// * `asset` - The asset to fetch the price for
+pub fn lastprice(env: &Env, config: &PriceSources, asset: &Asset) -> Option<AggregatePrice> {
+    let min_timestamp = match config.max_age {
+        0 => 0,
+        max_age => env.ledger().timestamp().saturating_sub(max_age),
+    };
+    aggregate(env, config, min_timestamp, |source| adapter::lastprice(env, source, asset))
+}
+
+/// Aggregate the prices of the sources of an asset at a timestamp
+/// # Arguments
+/// * `config` - The price sources of the asset
+/// * `asset` - The asset to fetch the price for
+/// * `timestamp` - The timestamp to fetch the price at
+pub fn price(env: &Env, config: &PriceSources, asset: &Asset, timestamp: u64) -> Option<AggregatePrice> {
+    aggregate(env, config, 0, |source| adapter::price(env, source, asset, timestamp))
+}
+
+/// Fetch a price from each source, ignoring sources that fail, have no price or are older than
+/// `min_timestamp`, and take the median of the prices within the tolerance of the median
+fn aggregate(
+    env: &Env,
+    config: &PriceSources,
+    min_timestamp: u64,
+    fetch: impl Fn(&Address) -> Option<PriceData>,
+) -> Option<AggregatePrice> {
+    let decimals = price::decimals(env);
+    let mut prices: Vec<(Address, PriceData)> = Vec::new(env);
+    for source in config.sources.iter() {
+        let price = match fetch(&source) {
+            Some(price) if price.timestamp >= min_timestamp => price,
+            _ => continue,
+        };
+        if let Some(source_decimals) = price::oracle_decimals(env, &source) {
+            let price = PriceData {
+                price: price::rescale(price.price, source_decimals, decimals),
+                timestamp: price.timestamp,
+            };
+            prices.push_back((source, price));
+        }
+    }
+    if prices.len() < config.quorum {
+        return None;
+    }
+
+    let median_price = median(env, &prices);
+    let mut accepted: Vec<(Address, PriceData)> = Vec::new(env);
+    for (source, price) in prices.iter() {
+        let deviation = (price.price - median_price).abs();
+        if deviation * SCALAR_7 <= config.tolerance * median_price {
+            accepted.push_back((source, price));
+        }
+    }
+    if accepted.len() < config.quorum {
+        return None;
+    }
+
+    let mut sources = Vec::new(env);
+    let mut timestamp = u64::MAX;
+    let mut low = i128::MAX;
+    let mut high = i128::MIN;
+    for (source, price) in accepted.iter() {
+        sources.push_back(source);
+        timestamp = timestamp.min(price.timestamp);
+        low = low.min(price.price);
+        high = high.max(price.price);
+    }
+    let median_price = median(env, &accepted);
+    let spread = if median_price > 0 { (high - low) * SCALAR_7 / median_price } else { 0 };
+    Some(AggregatePrice {
+        price: PriceData { price: median_price, timestamp },
+        sources,
+        spread,
+    })
+}
+
+/// The median of the prices, rounded down when the median falls between two prices
+fn median(env: &Env, prices: &Vec<(Address, PriceData)>) -> i128 {
+    let mut sorted: Vec<i128> = Vec::new(env);
+    for (_, price) in prices.iter() {
+        let index = sorted.binary_search(price.price).unwrap_or_else(|index| index);
+        sorted.insert(index, price.price);
+    }
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        sorted.get_unchecked(mid)
+    } else {
+        (sorted.get_unchecked(mid - 1) + sorted.get_unchecked(mid)) / 2
+    }
+}
@@ -0,0 +1,39 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Env};
+use crate::events::{self, EVENT_VERSION};
+use crate::{price, storage};
+
+/// The decimals price bounds are set in
+const BOUNDS_DECIMALS: u32 = 7;
+
+/// The range of acceptable prices of an asset
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceBounds {
+    /// The min acceptable price, with 7 decimals
+    pub min: i128,
+    /// The max acceptable price, with 7 decimals
+    pub max: i128,
+}
+
+/// Check a price against the bounds of the asset, publishing an event if it is out of bounds
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The price to check, in the decimals of the bridge oracle
+///
+/// Returns false if the price is below the min or above the max of the asset
+pub fn check(env: &Env, asset: &Asset, price: &PriceData) -> bool {
+    let bounds = match storage::get_bounds(env, asset) {
+        Some(bounds) => bounds,
+        None => return true,
+    };
+    let decimals = price::decimals(env);
+    let min = price::rescale(bounds.min, BOUNDS_DECIMALS, decimals);
+    let max = price::rescale(bounds.max, BOUNDS_DECIMALS, decimals);
+    if price.price < min || price.price > max {
+        events::PriceOutOfBounds { version: EVENT_VERSION, asset: asset.clone(), price: price.price, min, max }
+            .publish(env);
+        return false;
+    }
+    true
+}
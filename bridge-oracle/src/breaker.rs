@@ -0,0 +1,50 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Env};
+use crate::constants::SCALAR_7;
+use crate::events::{self, EVENT_VERSION};
+use crate::storage;
+
+/// The deviation circuit breaker of an asset
+#[derive(Clone)]
+#[contracttype]
+pub struct Breaker {
+    /// The max move of the price from the last accepted price, with 7 decimals
+    pub max_deviation: i128,
+    /// The time window in seconds a move is measured over
+    pub window: u64,
+}
+
+/// Check a price against the circuit breaker of the asset. Prices of assets without a breaker
+/// always pass
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The price to check
+///
+/// Returns false if the price moved more than the max deviation within the window
+pub fn check(env: &Env, asset: &Asset, price: &PriceData) -> bool {
+    if let (Some(last), Some(breaker)) = (storage::get_last_good_price(env, asset), storage::get_breaker(env, asset)) {
+        let in_window = price.timestamp.saturating_sub(last.timestamp) <= breaker.window;
+        if in_window && !within(&breaker, &last, price) {
+            events::BreakerTripped { version: EVENT_VERSION, asset: asset.clone(), price: price.price, last_price: last.price }
+                .publish(env);
+            return false;
+        }
+    }
+    true
+}
+
+/// Record a price that passed the checks as the last accepted price of the asset, so a frozen
+/// asset has a price to fall back on. Prices older than the last accepted price are ignored
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The accepted price
+pub fn record(env: &Env, asset: &Asset, price: &PriceData) {
+    match storage::get_last_good_price(env, asset) {
+        Some(last) if price.timestamp <= last.timestamp => {}
+        _ => storage::set_last_good_price(env, asset, price),
+    }
+}
+
+fn within(breaker: &Breaker, last: &PriceData, price: &PriceData) -> bool {
+    (price.price - last.price).abs() * SCALAR_7 <= breaker.max_deviation * last.price
+}
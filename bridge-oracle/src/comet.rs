@@ -0,0 +1,142 @@
+use crate::constants::{SCALAR_18, SCALAR_7};
+use crate::aggregate::AggregatePrice;
+use crate::price::{self, LastPrice, PriceStatus};
+use sep_40_oracle::{Asset, PriceData};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{vec, Address, Env, IntoVal, Val, Vec};
+
+/// ln(2) with 18 decimals
+const LN_2: i128 = 693_147_180_559_945_309;
+
+/// The tokens of a Comet pool, if it has at least two
+/// # Arguments
+/// * `pool` - The Comet pool
+pub fn tokens(env: &Env, pool: &Address) -> Option<Vec<Address>> {
+    price::try_invoke::<Vec<Address>>(env, pool, "get_tokens", vec![env])
+        .filter(|tokens| tokens.len() >= 2)
+}
+
+/// Fetch the fair value of a share of a Comet pool, the oracles the prices of its tokens came
+/// from and the largest spread of those prices.
+///
+/// A weighted pool holding balances `b_i` of tokens with weights `w_i` and oracle prices `p_i`
+/// is worth `prod((b_i * p_i / w_i) ^ w_i)`, which is the value of the pool once arbitrage has
+/// moved it to the oracle prices. Moving the balances only increases the invariant of the pool,
+/// so the value can't be inflated by trading against it, unlike the spot value of the balances.
+/// # Arguments
+/// * `pool` - The Comet pool, which is also its share token
+pub fn lastprice(env: &Env, pool: &Address) -> Option<AggregatePrice> {
+    let tokens = tokens(env, pool)?;
+    let supply = price::try_invoke::<i128>(env, pool, "get_total_supply", vec![env])?;
+    let share_decimals = price::try_invoke::<u32>(env, pool, "decimals", vec![env])?;
+    if supply <= 0 {
+        return None;
+    }
+
+    // the value of each token per unit of weight, in the decimals of the bridge oracle
+    let mut values: Vec<(i128, i128)> = Vec::new(env);
+    let mut sources: Vec<Address> = Vec::new(env);
+    let mut timestamp = u64::MAX;
+    let mut spread = 0;
+    for token in tokens.iter() {
+        let args: Vec<Val> = vec![env, token.into_val(env)];
+        let balance = price::try_invoke::<i128>(env, pool, "get_balance", args.clone())?;
+        let weight = price::try_invoke::<i128>(env, pool, "get_normalized_weight", args)?;
+        let token_decimals = price::try_invoke::<u32>(env, &token, "decimals", vec![env])?;
+        let (token_price, token_sources) = match price::fetch_last(env, &Asset::Stellar(token))? {
+            LastPrice {
+                price,
+                sources,
+                status: PriceStatus::Valid,
+                spread: token_spread,
+                ..
+            } => {
+                spread = spread.max(token_spread);
+                (price, sources)
+            }
+            _ => return None,
+        };
+        let value = balance.fixed_mul_floor(env, &token_price.price, &10i128.pow(token_decimals));
+        if weight <= 0 || value <= 0 {
+            return None;
+        }
+        values.push_back((value.fixed_div_floor(env, &weight, &SCALAR_7), weight));
+        timestamp = timestamp.min(token_price.timestamp);
+        for source in token_sources.iter() {
+            if !sources.contains(&source) {
+                sources.push_back(source);
+            }
+        }
+    }
+
+    // prod(v_i ^ w_i) = v_0 * exp(sum(w_i * ln(v_i / v_0))), as the normalized weights sum to 1
+    let (first, _) = values.get_unchecked(0);
+    let mut exponent: i128 = 0;
+    for (value, weight) in values.iter() {
+        let ratio = value.fixed_div_floor(env, &first, &SCALAR_18);
+        exponent += ln(ratio)?.fixed_mul_floor(env, &weight, &SCALAR_7);
+    }
+    let pool_value = first.fixed_mul_floor(env, &exp(exponent)?, &SCALAR_18);
+    let share_price = pool_value.fixed_mul_floor(env, &10i128.pow(share_decimals), &supply);
+    Some(AggregatePrice {
+        price: PriceData {
+            price: share_price,
+            timestamp: timestamp.min(env.ledger().timestamp()),
+        },
+        sources,
+        spread,
+    })
+}
+
+/// The natural logarithm of a positive number with 18 decimals
+fn ln(x: i128) -> Option<i128> {
+    if x <= 0 {
+        return None;
+    }
+    // ln(x) = k * ln(2) + ln(y), with y = x / 2^k in [1, 2)
+    let mut y = x;
+    let mut k: i128 = 0;
+    while y >= 2 * SCALAR_18 {
+        y /= 2;
+        k += 1;
+    }
+    while y < SCALAR_18 {
+        y *= 2;
+        k -= 1;
+    }
+    // ln(y) = 2 * atanh(z) = 2 * (z + z^3 / 3 + z^5 / 5 + ...), with z = (y - 1) / (y + 1)
+    let z = (y - SCALAR_18) * SCALAR_18 / (y + SCALAR_18);
+    let z_squared = z * z / SCALAR_18;
+    let mut term = z;
+    let mut sum: i128 = 0;
+    let mut n: i128 = 1;
+    while term != 0 {
+        sum += term / n;
+        term = term * z_squared / SCALAR_18;
+        n += 2;
+    }
+    Some(k * LN_2 + 2 * sum)
+}
+
+/// The exponential of a number with 18 decimals, or None if it overflows
+fn exp(x: i128) -> Option<i128> {
+    // exp(x) = 2^k * exp(r), with |r| <= ln(2) / 2
+    let half = if x < 0 { -LN_2 / 2 } else { LN_2 / 2 };
+    let k = (x + half) / LN_2;
+    let r = x - k * LN_2;
+    let mut term = SCALAR_18;
+    let mut sum = SCALAR_18;
+    let mut n: i128 = 1;
+    while term != 0 {
+        term = term * r / SCALAR_18 / n;
+        sum += term;
+        n += 1;
+    }
+    if k >= 0 {
+        sum.checked_mul(2i128.checked_pow(u32::try_from(k).ok()?)?)
+    } else if k > -127 {
+        Some(sum >> (-k))
+    } else {
+        Some(0)
+    }
+}
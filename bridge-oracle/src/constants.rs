@@ -0,0 +1,8 @@
+/// 1 with 7 decimal places
+pub const SCALAR_7: i128 = 1_0000000;
+
+/// The most decimals an i128 price can be quoted in
+pub const MAX_DECIMALS: u32 = 38;
+
+/// 1 with 18 decimal places
+pub const SCALAR_18: i128 = 1_000_000_000_000_000_000;
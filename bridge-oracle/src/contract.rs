@@ -1,6 +1,21 @@
 pub use sep_40_oracle::{Asset, PriceData};
-use soroban_sdk::{contract, contractclient, contractimpl, vec, Address, Env, Symbol, Vec, Val, IntoVal};
-use crate::storage;
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use crate::adapter::{self, Adapter};
+use crate::aggregate::PriceSources;
+use crate::bounds::{self, PriceBounds};
+use crate::cross::CrossLeg;
+use crate::errors::BridgeOracleError;
+use crate::fallback::FallbackOracle;
+use crate::mapping::{self, Mapping};
+use crate::overrides::{self, PriceOverride};
+use crate::breaker::Breaker;
+use crate::constants::MAX_DECIMALS;
+use crate::price::{DetailedPrice, FixedPrice, PriceStatus};
+use crate::ratio::{self, Rate, Ratio};
+use crate::twap::{self, TwapConfig, TwapState};
+use crate::events::{self, EVENT_VERSION};
+use crate::history::{self, MAX_HISTORY_SIZE};
+use crate::{comet, price, storage};
 
 #[contract]
 pub struct BridgeOracleContract;
@@ -12,16 +27,407 @@ pub trait BridgeOracle {
     /// # Arguments
     /// * `asset` - The asset to add
     /// * `to` - The asset to convert to
+    /// # Panics
+    /// If the asset is mapped to itself, the mapping closes a cycle of mappings, or `to` is not
+    /// quoted by the oracles it is priced by
     fn add_asset(e: Env, asset: Asset, to: Asset);
 
-    /// Fetch the number of decimals for the stellar oracle
+    /// (Admin only) Map an asset to an asset quoted the other way around, pricing it as 1 over
+    /// the price of that asset, such as a yen stablecoin mapped to a feed quoting yen per USD
+    /// # Arguments
+    /// * `asset` - The asset to map
+    /// * `to` - The asset whose inverse price the asset is priced as
+    /// # Panics
+    /// If the mapping points to the asset itself, closes a cycle of mappings, or the asset it
+    /// points to is not quoted by its oracles
+    fn add_inverse_asset(e: Env, asset: Asset, to: Asset);
+
+    /// (Admin only) Remove the mapping of an asset
+    /// # Arguments
+    /// * `asset` - The asset to remove the mapping for
+    fn remove_asset(e: Env, asset: Asset);
+
+    /// Fetch a page of the asset mappings, in the order they were added. Removing a mapping
+    /// moves the last mapping into its position
+    /// # Arguments
+    /// * `start` - The index of the first mapping
+    /// * `limit` - The max number of mappings to return
+    fn mappings(env: Env, start: u32, limit: u32) -> Vec<Mapping>;
+
+    /// (Admin only) Scale the price of an asset by a ratio, pricing it as the price of the
+    /// asset it is mapped to multiplied by the rate. Historical prices are scaled by the
+    /// current rate
+    /// # Arguments
+    /// * `asset` - The asset to set the ratio for
+    /// * `rate` - The fixed rate, the contract function returning the rate, or the Blend reserve
+    ///   whose b_rate is the rate
+    /// * `decimals` - The decimals of the rate
+    /// # Panics
+    /// If the decimals are above 18, or not 12 for a Blend b_rate, or the rate can't be fetched
+    /// or is not positive
+    fn set_ratio(e: Env, asset: Asset, rate: Rate, decimals: u32);
+
+    /// (Admin only) Remove the ratio of an asset
+    /// # Arguments
+    /// * `asset` - The asset to remove the ratio for
+    fn remove_ratio(e: Env, asset: Asset);
+
+    /// Fetch the ratio of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the ratio for
+    fn ratio(env: Env, asset: Asset) -> Option<Ratio>;
+
+    /// Fetch the base asset of the stellar oracle
+    fn base(env: Env) -> Asset;
+
+    /// Fetch the assets priced by a mapping, cross rate, TWAP or Comet pool of their own
+    fn assets(env: Env) -> Vec<Asset>;
+
+    /// Fetch the number of decimals prices are returned in, which are the decimals of the
+    /// stellar oracle. Prices from the other oracle are rescaled to these decimals
     fn decimals(env: Env) -> u32;
 
+    /// Fetch the resolution of the stellar oracle in seconds
+    fn resolution(env: Env) -> u32;
+
+    /// Fetch the price for the asset at a timestamp
+    /// # Arguments
+    /// * `asset` - The asset to fetch the price for
+    /// * `timestamp` - The timestamp to fetch the price at
+    fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData>;
+
+    /// Fetch the last prices for the asset. If the oracle keeps no history of the asset, the
+    /// prices recorded by the bridge oracle are returned
+    /// # Arguments
+    /// * `asset` - The asset to fetch the prices for
+    /// * `records` - The number of records to fetch
+    fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>>;
+
     /// Fetch the last price for the asset
     /// # Arguments
     /// * `asset` - The asset to fetch the price for
     fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
 
+    /// Fetch the last price for the asset, failing if it is missing, stale or deviated
+    /// # Arguments
+    /// * `asset` - The asset to fetch the price for
+    /// # Panics
+    /// If no price is found, the price is older than the max age of the asset, the price
+    /// moved more than the max deviation of the asset or the price is outside its bounds
+    fn lastprice_strict(env: Env, asset: Asset) -> PriceData;
+
+    /// Fetch the last price for the asset with where it came from, how long ago it was observed
+    /// upstream, how many oracles it was aggregated from and their spread. Prices that are stale,
+    /// deviated or out of bounds are returned with their status
+    /// # Arguments
+    /// * `asset` - The asset to fetch the price for
+    fn lastprice_detailed(env: Env, asset: Asset) -> Option<DetailedPrice>;
+
+    /// (Admin only) Set the max age of the price of an asset
+    /// # Arguments
+    /// * `asset` - The asset to set the max age for
+    /// * `max_age` - The max age of the price in seconds, or 0 to remove it
+    fn set_max_age(e: Env, asset: Asset, max_age: u64);
+
+    /// Fetch the max age of the price of an asset in seconds
+    /// # Arguments
+    /// * `asset` - The asset to fetch the max age for
+    fn max_age(env: Env, asset: Asset) -> Option<u64>;
+
+    /// (Admin only) Set the oracles an asset is priced by, taking the median of their prices
+    /// # Arguments
+    /// * `asset` - The asset to set the sources for, as quoted by the sources
+    /// * `sources` - The oracles quoting the asset, or none to remove the sources
+    /// * `quorum` - The number of sources that must agree on a price
+    /// * `tolerance` - The max deviation of a source from the median, with 7 decimals
+    /// * `max_age` - The max age of a source price in seconds, or 0 for no max age
+    /// # Panics
+    /// If the quorum is 0 or more than the number of sources, or the tolerance is negative
+    fn set_price_sources(e: Env, asset: Asset, sources: Vec<Address>, quorum: u32, tolerance: i128, max_age: u64);
+
+    /// Fetch the price sources of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the sources for, as quoted by the sources
+    fn price_sources(env: Env, asset: Asset) -> Option<PriceSources>;
+
+    /// Fetch the oracles that took part in the last price for the asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the oracles for
+    fn lastprice_sources(env: Env, asset: Asset) -> Vec<Address>;
+
+    /// (Admin only) Set the oracles used in order when the price of an asset is missing or stale
+    /// # Arguments
+    /// * `asset` - The asset to set the fallback oracles for
+    /// * `fallbacks` - The fallback oracles, quoting the bridge asset of the asset, or none to remove them
+    fn set_fallbacks(e: Env, asset: Asset, fallbacks: Vec<FallbackOracle>);
+
+    /// Fetch the fallback oracles of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the fallback oracles for
+    fn fallbacks(env: Env, asset: Asset) -> Vec<FallbackOracle>;
+
+    /// (Admin only) Price an asset as the product of the prices of the legs, where inverse legs
+    /// divide instead of multiply. Each leg is priced like its asset, following the inverse
+    /// mapping and ratio of the asset. The cross rate takes precedence over the bridge asset
+    /// # Arguments
+    /// * `asset` - The asset to set the cross rate for
+    /// * `legs` - The legs of the cross rate, or none to remove it
+    /// # Panics
+    /// If there is only one leg
+    fn set_cross_rate(e: Env, asset: Asset, legs: Vec<CrossLeg>);
+
+    /// Fetch the cross rate legs of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the cross rate for
+    fn cross_rate(env: Env, asset: Asset) -> Option<Vec<CrossLeg>>;
+
+    /// (Admin only) Set a fixed price for an asset, used instead of an oracle for assets
+    /// bridged to it
+    /// # Arguments
+    /// * `asset` - The asset to set the fixed price for
+    /// * `price` - The price of the asset
+    /// * `decimals` - The decimals of the price
+    /// # Panics
+    /// If the price is not positive, the decimals are above 38, or the price can't be quoted in the
+    /// decimals of the bridge oracle
+    fn set_fixed_price(e: Env, asset: Asset, price: i128, decimals: u32);
+
+    /// (Admin only) Remove the fixed price of an asset
+    /// # Arguments
+    /// * `asset` - The asset to remove the fixed price for
+    fn remove_fixed_price(e: Env, asset: Asset);
+
+    /// Fetch the fixed price of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the fixed price for
+    fn fixed_price(env: Env, asset: Asset) -> Option<FixedPrice>;
+
+    /// (Admin only) Set the range of acceptable prices of an asset. Prices outside the range
+    /// are rejected
+    /// # Arguments
+    /// * `asset` - The asset to set the bounds for
+    /// * `min` - The min acceptable price, with 7 decimals
+    /// * `max` - The max acceptable price, with 7 decimals
+    /// # Panics
+    /// If the min is negative or the max is not above the min
+    fn set_bounds(e: Env, asset: Asset, min: i128, max: i128);
+
+    /// (Admin only) Remove the price bounds of an asset
+    /// # Arguments
+    /// * `asset` - The asset to remove the bounds for
+    fn remove_bounds(e: Env, asset: Asset);
+
+    /// Fetch the price bounds of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the bounds for
+    fn bounds(env: Env, asset: Asset) -> Option<PriceBounds>;
+
+    /// (Admin only) Set the guardian, who can freeze assets and override their prices
+    /// # Arguments
+    /// * `guardian` - The guardian address
+    fn set_guardian(e: Env, guardian: Address);
+
+    /// Fetch the guardian address
+    fn guardian(env: Env) -> Option<Address>;
+
+    /// (Admin only) Set the circuit breaker of an asset. Prices that move more than the max
+    /// deviation from the last accepted price within the window are rejected
+    /// # Arguments
+    /// * `asset` - The asset to set the circuit breaker for
+    /// * `max_deviation` - The max move of the price with 7 decimals, or 0 to remove the breaker
+    /// * `window` - The time window in seconds a move is measured over
+    /// # Panics
+    /// If the max deviation is negative
+    fn set_breaker(e: Env, asset: Asset, max_deviation: i128, window: u64);
+
+    /// Fetch the circuit breaker of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the circuit breaker for
+    fn breaker(env: Env, asset: Asset) -> Option<Breaker>;
+
+    /// Fetch the last accepted price of an asset, as recorded by the last `poke` of it
+    /// # Arguments
+    /// * `asset` - The asset to fetch the last accepted price for
+    fn last_good_price(env: Env, asset: Asset) -> Option<PriceData>;
+
+    /// (Guardian only) Freeze an asset, so the last accepted price is returned until the admin
+    /// unfreezes it
+    /// # Arguments
+    /// * `asset` - The asset to freeze
+    /// # Panics
+    /// If no guardian is set
+    fn freeze(e: Env, asset: Asset);
+
+    /// (Admin only) Unfreeze an asset
+    /// # Arguments
+    /// * `asset` - The asset to unfreeze
+    fn unfreeze(e: Env, asset: Asset);
+
+    /// Check if an asset is frozen
+    /// # Arguments
+    /// * `asset` - The asset to check
+    fn frozen(env: Env, asset: Asset) -> bool;
+
+    /// (Admin only) Set the band override prices are limited to, around the last accepted price
+    /// of the asset
+    /// # Arguments
+    /// * `band` - The max distance of an override from the last accepted price with 7 decimals,
+    ///   or 0 to disable overrides
+    /// # Panics
+    /// If the band is negative
+    fn set_override_band(e: Env, band: i128);
+
+    /// Fetch the band override prices are limited to
+    fn override_band(env: Env) -> Option<i128>;
+
+    /// (Guardian only) Override the price of an asset until it expires. While it is active the
+    /// override is returned as the last price of the asset
+    /// # Arguments
+    /// * `asset` - The asset to override the price for
+    /// * `price` - The price of the asset, in the decimals of the bridge oracle
+    /// * `expires_at` - The timestamp the override expires at
+    /// # Panics
+    /// If no guardian is set, the override has already expired, the asset has no last accepted
+    /// price, or the price is outside the override band around it or the bounds of the asset
+    fn set_override(e: Env, asset: Asset, price: i128, expires_at: u64);
+
+    /// (Admin only) Remove the override of an asset
+    /// # Arguments
+    /// * `asset` - The asset to remove the override for
+    fn remove_override(e: Env, asset: Asset);
+
+    /// Fetch the override of an asset, including an expired one
+    /// # Arguments
+    /// * `asset` - The asset to fetch the override for
+    fn price_override(env: Env, asset: Asset) -> Option<PriceOverride>;
+
+    /// (Admin only) Price a stellar asset by its time-weighted average price in a Soroswap pair,
+    /// in the token it is paired with. Soroswap pairs expose no price cumulatives, so the bridge
+    /// oracle accumulates its own observations of the pair, and a price is only available once
+    /// at least 4 observations cover the window, each no more than a quarter of the window apart
+    /// # Arguments
+    /// * `asset` - The stellar asset to set the TWAP source for
+    /// * `pair` - The Soroswap pair of the asset
+    /// * `window` - The time window in seconds the price is averaged over
+    /// * `min_liquidity` - The min reserve of the paired token
+    /// # Panics
+    /// If the asset is not in the pair, the window is 0 or the min liquidity is negative
+    fn set_twap(e: Env, asset: Asset, pair: Address, window: u64, min_liquidity: i128);
+
+    /// (Admin only) Remove the TWAP source of an asset and its observations
+    /// # Arguments
+    /// * `asset` - The asset to remove the TWAP source for
+    fn remove_twap(e: Env, asset: Asset);
+
+    /// Fetch the TWAP source of an asset
+    /// # Arguments
+    /// * `asset` - The asset to fetch the TWAP source for
+    fn twap(env: Env, asset: Asset) -> Option<TwapConfig>;
+
+    /// Record an observation of the pair of an asset with a TWAP source. Anyone can call this
+    /// or `poke` to keep the observations current, and one of them must be called at least every
+    /// quarter of the window, or the observations are restarted. Reading the price of the asset
+    /// does not observe its pair
+    /// # Arguments
+    /// * `asset` - The asset to observe
+    fn observe(e: Env, asset: Asset) -> Option<TwapState>;
+
+    /// (Admin only) Route an asset to an oracle. The asset is the one quoted by the oracle, after
+    /// bridge mappings are applied
+    /// # Arguments
+    /// * `asset` - The asset to route
+    /// * `oracle` - The oracle contract address the asset is priced by
+    fn set_route(e: Env, asset: Asset, oracle: Address);
+
+    /// (Admin only) Remove the route of an asset, so it uses the default route of its variant
+    /// # Arguments
+    /// * `asset` - The asset to remove the route for
+    fn remove_route(e: Env, asset: Asset);
+
+    /// Fetch the oracle an asset is routed to, or None if it uses the default route
+    /// # Arguments
+    /// * `asset` - The asset to fetch the route for
+    fn route(env: Env, asset: Asset) -> Option<Address>;
+
+    /// (Admin only) Rotate the default oracles
+    /// # Arguments
+    /// * `stellar_oracle` - The oracle contract address for stellar assets
+    /// * `other_oracle` - The oracle contract address for other assets
+    fn set_oracles(e: Env, stellar_oracle: Address, other_oracle: Address);
+
+    /// (Admin only) Price the shares of a Comet pool at the fair value of the pool, from the
+    /// prices of its tokens, its weights and its balances
+    /// # Arguments
+    /// * `pool` - The Comet pool, which is also its share token
+    /// # Panics
+    /// If the pool does not have at least two tokens
+    fn set_comet_pool(e: Env, pool: Address);
+
+    /// (Admin only) Stop pricing the shares of a Comet pool at fair value
+    /// # Arguments
+    /// * `pool` - The Comet pool
+    fn remove_comet_pool(e: Env, pool: Address);
+
+    /// Check if the shares of a Comet pool are priced at fair value
+    /// # Arguments
+    /// * `pool` - The Comet pool
+    fn is_comet_pool(env: Env, pool: Address) -> bool;
+
+    /// (Admin only) Enable or disable the price cache. While enabled, `poke` keeps the last price
+    /// of each asset in temporary storage for the rest of the ledger, and reads in that ledger use
+    /// it instead of resolving the price again, so config changes take effect from the next
+    /// ledger or poke. Overrides and freezes take effect immediately
+    /// # Arguments
+    /// * `enabled` - If the cache is enabled
+    fn set_cache(e: Env, enabled: bool);
+
+    /// Check if the price cache is enabled
+    fn cache(env: Env) -> bool;
+
+    /// (Admin only) Set the interface an oracle follows, for oracles that differ from SEP-40
+    /// # Arguments
+    /// * `oracle` - The oracle contract address
+    /// * `adapter` - The interface of the oracle
+    /// # Panics
+    /// If the oracle does not follow the interface
+    fn set_adapter(e: Env, oracle: Address, adapter: Adapter);
+
+    /// Fetch the interface an oracle follows
+    /// # Arguments
+    /// * `oracle` - The oracle contract address
+    fn adapter(env: Env, oracle: Address) -> Adapter;
+
+    /// (Admin only) Set the number of valid prices recorded for each asset. Prices are recorded
+    /// when the asset is poked, at most once per timestamp
+    /// # Arguments
+    /// * `size` - The number of prices to keep, or 0 to stop recording
+    /// # Panics
+    /// If the size is larger than the max history size
+    fn set_history_size(e: Env, size: u32);
+
+    /// Fetch the number of prices recorded for each asset
+    fn history_size(env: Env) -> u32;
+
+    /// Fetch the time-weighted average of the prices recorded for the asset. Each price is
+    /// weighted by the time until the next one, and the last price by the time until now
+    /// # Arguments
+    /// * `asset` - The asset to fetch the average for
+    /// * `records` - The number of recorded prices to average
+    fn history_twap(env: Env, asset: Asset, records: u32) -> Option<PriceData>;
+
+    /// Resolve the last prices of the assets, observing the pairs of those with a TWAP source
+    /// and recording the valid prices as their last accepted price and in their history.
+    /// Reading a price never records it
+    /// # Arguments
+    /// * `assets` - The assets to resolve the prices for
+    fn poke(e: Env, assets: Vec<Asset>);
+
+    /// Fetch the oracle contract address for stellar assets
+    fn stellar_oracle(env: Env) -> Address;
+
+    /// Fetch the oracle contract address for other assets
+    fn other_oracle(env: Env) -> Address;
+
     /// (Admin only) Set the admin address
     /// # Arguments
     /// * `new_admin` - The new admin address
@@ -42,8 +448,13 @@ impl BridgeOracleContract {
         storage::set_admin(&e, &admin);
         storage::set_stellar_oracle(&e, &stellar_oracle);
         storage::set_other_oracle(&e, &other_oracle);
+        price::store_decimals(&e, &stellar_oracle);
+        price::store_decimals(&e, &other_oracle);
+        // USD is the base of the oracles
+        let usd = Asset::Other(Symbol::new(&e, "USD"));
+        storage::set_fixed_price(&e, &usd, &FixedPrice { price: 1, decimals: 0 });
 
-        e.events().publish(("BridgeOracle", Symbol::new(&e, "init")), (admin.clone(), stellar_oracle.clone(), other_oracle.clone()));
+        events::Init { version: EVENT_VERSION, admin, stellar_oracle, other_oracle }.publish(&e);
     }
 }
 
@@ -54,39 +465,558 @@ impl BridgeOracle for BridgeOracleContract {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
-        storage::set_bridge_asset(&e, &asset, &to);
+        if !mapping::is_valid(&e, &asset, &to) {
+            panic_with_error!(e, BridgeOracleError::InvalidMappingError);
+        }
+        mapping::add(&e, &asset, &to, false);
+
+        events::AddAsset { version: EVENT_VERSION, asset, to }.publish(&e);
+    }
+
+    fn add_inverse_asset(e: Env, asset: Asset, to: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if !mapping::is_valid(&e, &asset, &to) {
+            panic_with_error!(e, BridgeOracleError::InvalidMappingError);
+        }
+        mapping::add(&e, &asset, &to, true);
+
+        events::AddInverseAsset { version: EVENT_VERSION, asset, to }.publish(&e);
+    }
+
+    fn remove_asset(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        mapping::remove(&e, &asset);
+
+        events::RemoveAsset { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn mappings(env: Env, start: u32, limit: u32) -> Vec<Mapping> {
+        storage::extend_instance(&env);
+        mapping::page(&env, start, limit)
+    }
+
+    fn set_ratio(e: Env, asset: Asset, rate: Rate, decimals: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        let ratio = Ratio { rate, decimals };
+        if !ratio::is_valid(&e, &ratio) {
+            panic_with_error!(e, BridgeOracleError::InvalidRatioError);
+        }
+        storage::set_ratio(&e, &asset, &ratio);
+
+        events::SetRatio { version: EVENT_VERSION, asset, ratio }.publish(&e);
+    }
+
+    fn remove_ratio(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_ratio(&e, &asset);
 
-        e.events().publish(("BridgeOracle", Symbol::new(&e, "add_asset")), (asset.clone(), to.clone()));
+        events::RemoveRatio { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn ratio(env: Env, asset: Asset) -> Option<Ratio> {
+        storage::extend_instance(&env);
+        storage::get_ratio(&env, &asset)
+    }
+
+    fn base(env: Env) -> Asset {
+        storage::extend_instance(&env);
+        price::base(&env)
+    }
+
+    fn assets(env: Env) -> Vec<Asset> {
+        storage::extend_instance(&env);
+        storage::get_assets(&env)
     }
 
     fn decimals(env: Env) -> u32 {
         storage::extend_instance(&env);
-        let oracle = storage::get_stellar_oracle(&env);
-        env.invoke_contract::<u32>(&oracle, &Symbol::new(&env, "decimals"), vec![&env])
+        price::decimals(&env)
+    }
+
+    fn resolution(env: Env) -> u32 {
+        storage::extend_instance(&env);
+        price::resolution(&env)
+    }
+
+    fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        storage::extend_instance(&env);
+        price::fetch_price_at(&env, &asset, timestamp)
+    }
+
+    fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        storage::extend_instance(&env);
+        price::fetch_prices(&env, &asset, records)
     }
 
     fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
         storage::extend_instance(&env);
-        let to_asset = storage::get_bridge_asset(&env, &asset);
+        price::load_price(&env, &asset)
+    }
+
+    fn lastprice_strict(env: Env, asset: Asset) -> PriceData {
+        storage::extend_instance(&env);
+        let last = price::fetch_last(&env, &asset).unwrap_or_else(|| {
+            panic_with_error!(env, BridgeOracleError::PriceNotFoundError);
+        });
+        match last.status {
+            PriceStatus::Valid => last.price,
+            PriceStatus::Stale => panic_with_error!(env, BridgeOracleError::StalePriceError),
+            PriceStatus::Deviated => panic_with_error!(env, BridgeOracleError::PriceDeviationError),
+            PriceStatus::OutOfBounds => panic_with_error!(env, BridgeOracleError::PriceOutOfBoundsError),
+        }
+    }
+
+    fn lastprice_detailed(env: Env, asset: Asset) -> Option<DetailedPrice> {
+        storage::extend_instance(&env);
+        price::fetch_detailed(&env, &asset)
+    }
+
+    fn set_max_age(e: Env, asset: Asset, max_age: u64) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if max_age == 0 {
+            storage::remove_max_age(&e, &asset);
+        } else {
+            storage::set_max_age(&e, &asset, &max_age);
+        }
+
+        events::SetMaxAge { version: EVENT_VERSION, asset, max_age }.publish(&e);
+    }
 
-        match to_asset.clone() {
-            Asset::Stellar(_) => {
-                let stellar_oracle = storage::get_stellar_oracle(&env);
-                let args: Vec<Val> = vec![&env, to_asset.into_val(&env)];
-                env.invoke_contract::<Option<PriceData>>(&stellar_oracle, &Symbol::new(&env, "lastprice"), args)
+    fn max_age(env: Env, asset: Asset) -> Option<u64> {
+        storage::extend_instance(&env);
+        storage::get_max_age(&env, &asset)
+    }
+
+    fn set_price_sources(e: Env, asset: Asset, sources: Vec<Address>, quorum: u32, tolerance: i128, max_age: u64) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if sources.is_empty() {
+            storage::remove_price_sources(&e, &asset);
+        } else {
+            if quorum == 0 || quorum > sources.len() || tolerance < 0 {
+                panic_with_error!(e, BridgeOracleError::InvalidPriceSourcesError);
+            }
+            let config = PriceSources { sources: sources.clone(), quorum, tolerance, max_age };
+            storage::set_price_sources(&e, &asset, &config);
+            for source in sources.iter() {
+                price::store_decimals(&e, &source);
             }
-            Asset::Other(name) => {
-                if name == Symbol::new(&env, "USD") {
-                    let timestamp = env.ledger().timestamp();
-                    Some(PriceData {price: 1_00_000_000_000_000, timestamp})
-                }
-                else {
-                    let other_oracle = storage::get_other_oracle(&env);
-                    let args: Vec<Val> = vec![&env, to_asset.into_val(&env)];
-                    env.invoke_contract::<Option<PriceData>>(&other_oracle, &Symbol::new(&env, "lastprice"), args)
-                }   
+        }
+
+        events::SetPriceSources { version: EVENT_VERSION, asset, sources, quorum, tolerance, max_age }.publish(&e);
+    }
+
+    fn price_sources(env: Env, asset: Asset) -> Option<PriceSources> {
+        storage::extend_instance(&env);
+        storage::get_price_sources(&env, &asset)
+    }
+
+    fn lastprice_sources(env: Env, asset: Asset) -> Vec<Address> {
+        storage::extend_instance(&env);
+        price::fetch_sources(&env, &asset)
+    }
+
+    fn set_fallbacks(e: Env, asset: Asset, fallbacks: Vec<FallbackOracle>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if fallbacks.is_empty() {
+            storage::remove_fallbacks(&e, &asset);
+        } else {
+            storage::set_fallbacks(&e, &asset, &fallbacks);
+            for fallback in fallbacks.iter() {
+                price::store_decimals(&e, &fallback.oracle);
+            }
+        }
+
+        events::SetFallbacks { version: EVENT_VERSION, asset, fallbacks }.publish(&e);
+    }
+
+    fn fallbacks(env: Env, asset: Asset) -> Vec<FallbackOracle> {
+        storage::extend_instance(&env);
+        storage::get_fallbacks(&env, &asset)
+    }
+
+    fn set_cross_rate(e: Env, asset: Asset, legs: Vec<CrossLeg>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if legs.is_empty() {
+            storage::remove_cross_rate(&e, &asset);
+        } else {
+            if legs.len() < 2 {
+                panic_with_error!(e, BridgeOracleError::InvalidCrossRateError);
             }
+            storage::set_cross_rate(&e, &asset, &legs);
+        }
+        price::sync_listed(&e, &asset);
+
+        events::SetCrossRate { version: EVENT_VERSION, asset, legs }.publish(&e);
+    }
+
+    fn cross_rate(env: Env, asset: Asset) -> Option<Vec<CrossLeg>> {
+        storage::extend_instance(&env);
+        storage::get_cross_rate(&env, &asset)
+    }
+
+    fn set_fixed_price(e: Env, asset: Asset, price: i128, decimals: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if price <= 0 || decimals > MAX_DECIMALS {
+            panic_with_error!(e, BridgeOracleError::InvalidFixedPriceError);
+        }
+        match price::checked_rescale(price, decimals, price::decimals(&e)) {
+            Some(scaled) if scaled > 0 => (),
+            _ => panic_with_error!(e, BridgeOracleError::InvalidFixedPriceError),
+        }
+        storage::set_fixed_price(&e, &asset, &FixedPrice { price, decimals });
+
+        events::SetFixedPrice { version: EVENT_VERSION, asset, price, decimals }.publish(&e);
+    }
+
+    fn remove_fixed_price(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_fixed_price(&e, &asset);
+
+        events::RemoveFixedPrice { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn fixed_price(env: Env, asset: Asset) -> Option<FixedPrice> {
+        storage::extend_instance(&env);
+        storage::get_fixed_price(&env, &asset)
+    }
+
+    fn set_bounds(e: Env, asset: Asset, min: i128, max: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if min < 0 || max <= min {
+            panic_with_error!(e, BridgeOracleError::InvalidBoundsError);
+        }
+        storage::set_bounds(&e, &asset, &PriceBounds { min, max });
+
+        events::SetBounds { version: EVENT_VERSION, asset, min, max }.publish(&e);
+    }
+
+    fn remove_bounds(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_bounds(&e, &asset);
+
+        events::RemoveBounds { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn bounds(env: Env, asset: Asset) -> Option<PriceBounds> {
+        storage::extend_instance(&env);
+        storage::get_bounds(&env, &asset)
+    }
+
+    fn set_guardian(e: Env, guardian: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::set_guardian(&e, &guardian);
+
+        events::SetGuardian { version: EVENT_VERSION, guardian }.publish(&e);
+    }
+
+    fn guardian(env: Env) -> Option<Address> {
+        storage::extend_instance(&env);
+        storage::get_guardian(&env)
+    }
+
+    fn set_breaker(e: Env, asset: Asset, max_deviation: i128, window: u64) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if max_deviation < 0 {
+            panic_with_error!(e, BridgeOracleError::InvalidBreakerError);
+        }
+        if max_deviation == 0 {
+            storage::remove_breaker(&e, &asset);
+        } else {
+            storage::set_breaker(&e, &asset, &Breaker { max_deviation, window });
+        }
+
+        events::SetBreaker { version: EVENT_VERSION, asset, max_deviation, window }.publish(&e);
+    }
+
+    fn breaker(env: Env, asset: Asset) -> Option<Breaker> {
+        storage::extend_instance(&env);
+        storage::get_breaker(&env, &asset)
+    }
+
+    fn last_good_price(env: Env, asset: Asset) -> Option<PriceData> {
+        storage::extend_instance(&env);
+        storage::get_last_good_price(&env, &asset)
+    }
+
+    fn freeze(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let guardian = storage::get_guardian(&e).unwrap_or_else(|| {
+            panic_with_error!(e, BridgeOracleError::GuardianNotSetError);
+        });
+        guardian.require_auth();
+        storage::set_frozen(&e, &asset, true);
+
+        events::SetFrozen { version: EVENT_VERSION, asset, frozen: true }.publish(&e);
+    }
+
+    fn unfreeze(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::set_frozen(&e, &asset, false);
+
+        events::SetFrozen { version: EVENT_VERSION, asset, frozen: false }.publish(&e);
+    }
+
+    fn frozen(env: Env, asset: Asset) -> bool {
+        storage::extend_instance(&env);
+        storage::get_frozen(&env, &asset)
+    }
+
+    fn set_override_band(e: Env, band: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if band < 0 {
+            panic_with_error!(e, BridgeOracleError::InvalidOverrideError);
+        }
+        if band == 0 {
+            storage::remove_override_band(&e);
+        } else {
+            storage::set_override_band(&e, &band);
+        }
+
+        events::SetOverrideBand { version: EVENT_VERSION, band }.publish(&e);
+    }
+
+    fn override_band(env: Env) -> Option<i128> {
+        storage::extend_instance(&env);
+        storage::get_override_band(&env)
+    }
+
+    fn set_override(e: Env, asset: Asset, price: i128, expires_at: u64) {
+        storage::extend_instance(&e);
+        let guardian = storage::get_guardian(&e).unwrap_or_else(|| {
+            panic_with_error!(e, BridgeOracleError::GuardianNotSetError);
+        });
+        guardian.require_auth();
+        if price <= 0 || expires_at <= e.ledger().timestamp() || !overrides::is_within_band(&e, &asset, price) {
+            panic_with_error!(e, BridgeOracleError::InvalidOverrideError);
+        }
+        if !bounds::check(&e, &asset, &PriceData { price, timestamp: e.ledger().timestamp() }) {
+            panic_with_error!(e, BridgeOracleError::PriceOutOfBoundsError);
+        }
+        storage::set_override(&e, &asset, &PriceOverride { price, expires_at });
+
+        events::SetOverride { version: EVENT_VERSION, asset, price, expires_at }.publish(&e);
+    }
+
+    fn remove_override(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_override(&e, &asset);
+
+        events::RemoveOverride { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn price_override(env: Env, asset: Asset) -> Option<PriceOverride> {
+        storage::extend_instance(&env);
+        storage::get_override(&env, &asset)
+    }
+
+    fn set_twap(e: Env, asset: Asset, pair: Address, window: u64, min_liquidity: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if window == 0 || min_liquidity < 0 || twap::quote_token(&e, &asset, &pair).is_none() {
+            panic_with_error!(e, BridgeOracleError::InvalidTwapError);
+        }
+        storage::remove_twap(&e, &asset);
+        storage::set_twap(&e, &asset, &TwapConfig { pair: pair.clone(), window, min_liquidity });
+        price::sync_listed(&e, &asset);
+
+        events::SetTwap { version: EVENT_VERSION, asset, pair, window, min_liquidity }.publish(&e);
+    }
+
+    fn remove_twap(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_twap(&e, &asset);
+        price::sync_listed(&e, &asset);
+
+        events::RemoveTwap { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn twap(env: Env, asset: Asset) -> Option<TwapConfig> {
+        storage::extend_instance(&env);
+        storage::get_twap(&env, &asset)
+    }
+
+    fn observe(e: Env, asset: Asset) -> Option<TwapState> {
+        storage::extend_instance(&e);
+        let config = storage::get_twap(&e, &asset)?;
+        twap::observe(&e, &asset, &config).map(|(_, state)| state)
+    }
+
+    fn set_route(e: Env, asset: Asset, oracle: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::set_route(&e, &asset, &oracle);
+        price::store_decimals(&e, &oracle);
+        price::sync_listed(&e, &asset);
+
+        events::SetRoute { version: EVENT_VERSION, asset, oracle }.publish(&e);
+    }
+
+    fn remove_route(e: Env, asset: Asset) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_route(&e, &asset);
+        price::sync_listed(&e, &asset);
+
+        events::RemoveRoute { version: EVENT_VERSION, asset }.publish(&e);
+    }
+
+    fn route(env: Env, asset: Asset) -> Option<Address> {
+        storage::extend_instance(&env);
+        storage::get_route(&env, &asset)
+    }
+
+    fn set_oracles(e: Env, stellar_oracle: Address, other_oracle: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::set_stellar_oracle(&e, &stellar_oracle);
+        storage::set_other_oracle(&e, &other_oracle);
+        price::store_decimals(&e, &stellar_oracle);
+        price::store_decimals(&e, &other_oracle);
+
+        events::SetOracles { version: EVENT_VERSION, stellar_oracle, other_oracle }.publish(&e);
+    }
+
+    fn set_comet_pool(e: Env, pool: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if comet::tokens(&e, &pool).is_none() {
+            panic_with_error!(e, BridgeOracleError::InvalidCometPoolError);
         }
+        storage::set_comet_pool(&e, &pool);
+        price::sync_listed(&e, &Asset::Stellar(pool.clone()));
+
+        events::SetCometPool { version: EVENT_VERSION, pool }.publish(&e);
+    }
+
+    fn remove_comet_pool(e: Env, pool: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::remove_comet_pool(&e, &pool);
+        price::sync_listed(&e, &Asset::Stellar(pool.clone()));
+
+        events::RemoveCometPool { version: EVENT_VERSION, pool }.publish(&e);
+    }
+
+    fn is_comet_pool(env: Env, pool: Address) -> bool {
+        storage::extend_instance(&env);
+        storage::get_comet_pool(&env, &pool)
+    }
+
+    fn set_cache(e: Env, enabled: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::set_cache(&e, enabled);
+
+        events::SetCache { version: EVENT_VERSION, enabled }.publish(&e);
+    }
+
+    fn cache(env: Env) -> bool {
+        storage::extend_instance(&env);
+        storage::get_cache(&env)
+    }
+
+    fn set_adapter(e: Env, oracle: Address, adapter: Adapter) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if !adapter::is_valid(&e, &oracle, adapter) {
+            panic_with_error!(e, BridgeOracleError::InvalidAdapterError);
+        }
+        if adapter == Adapter::Sep40 {
+            storage::remove_adapter(&e, &oracle);
+        } else {
+            storage::set_adapter(&e, &oracle, &adapter);
+        }
+        price::store_decimals(&e, &oracle);
+
+        events::SetAdapter { version: EVENT_VERSION, oracle, adapter }.publish(&e);
+    }
+
+    fn adapter(env: Env, oracle: Address) -> Adapter {
+        storage::extend_instance(&env);
+        storage::get_adapter(&env, &oracle)
+    }
+
+    fn set_history_size(e: Env, size: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if size > MAX_HISTORY_SIZE {
+            panic_with_error!(e, BridgeOracleError::InvalidHistorySizeError);
+        }
+        storage::set_history_size(&e, size);
+
+        events::SetHistorySize { version: EVENT_VERSION, size }.publish(&e);
+    }
+
+    fn history_size(env: Env) -> u32 {
+        storage::extend_instance(&env);
+        storage::get_history_size(&env)
+    }
+
+    fn history_twap(env: Env, asset: Asset, records: u32) -> Option<PriceData> {
+        storage::extend_instance(&env);
+        history::twap(&env, &asset, records)
+    }
+
+    fn poke(e: Env, assets: Vec<Asset>) {
+        storage::extend_instance(&e);
+        for asset in assets.iter() {
+            price::record(&e, &asset);
+        }
+    }
+
+    fn stellar_oracle(env: Env) -> Address {
+        storage::extend_instance(&env);
+        storage::get_stellar_oracle(&env)
+    }
+
+    fn other_oracle(env: Env) -> Address {
+        storage::extend_instance(&env);
+        storage::get_other_oracle(&env)
     }
 
     fn set_admin(e: Env, new_admin: Address) {
@@ -95,6 +1025,6 @@ impl BridgeOracle for BridgeOracleContract {
         admin.require_auth();
         storage::set_admin(&e, &new_admin);
 
-        e.events().publish(("BridgeOracle", Symbol::new(&e, "set_admin")), (new_admin.clone(),));
+        events::SetAdmin { version: EVENT_VERSION, admin: new_admin }.publish(&e);
     }
 }
\ No newline at end of file
@@ -0,0 +1,33 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contracttype, Env, Vec};
+use crate::price;
+
+/// A leg of a cross rate
+#[derive(Clone)]
+#[contracttype]
+pub struct CrossLeg {
+    /// The asset whose price is part of the cross rate
+    pub asset: Asset,
+    /// If the cross rate is divided by the price of the asset, instead of multiplied
+    pub inverse: bool,
+}
+
+/// Compose the prices of the legs of a cross rate, with the oldest timestamp of the legs
+/// # Arguments
+/// * `legs` - The legs of the cross rate
+/// * `prices` - The price of each leg, in the decimals of the bridge oracle
+pub fn compose(env: &Env, legs: &Vec<CrossLeg>, prices: &Vec<PriceData>) -> Option<PriceData> {
+    let scalar = 10i128.pow(price::decimals(env));
+    let mut rate = scalar;
+    let mut timestamp = u64::MAX;
+    for (leg, price) in legs.iter().zip(prices.iter()) {
+        rate = if leg.inverse {
+            rate.fixed_div_floor(price.price, scalar)?
+        } else {
+            rate.fixed_mul_floor(price.price, scalar)?
+        };
+        timestamp = timestamp.min(price.timestamp);
+    }
+    Some(PriceData { price: rate, timestamp })
+}
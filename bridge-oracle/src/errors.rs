@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BridgeOracleError {
+    PriceNotFoundError = 1401,
+    StalePriceError = 1402,
+    InvalidPriceSourcesError = 1403,
+    InvalidCrossRateError = 1404,
+    InvalidFixedPriceError = 1405,
+    PriceDeviationError = 1406,
+    GuardianNotSetError = 1407,
+    InvalidBreakerError = 1408,
+    InvalidTwapError = 1409,
+    InvalidMappingError = 1410,
+    InvalidOverrideError = 1411,
+    InvalidBoundsError = 1412,
+    PriceOutOfBoundsError = 1413,
+    InvalidRatioError = 1414,
+    InvalidCometPoolError = 1415,
+    InvalidAdapterError = 1416,
+    InvalidHistorySizeError = 1417,
+}
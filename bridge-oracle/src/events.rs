@@ -0,0 +1,353 @@
+use crate::adapter::Adapter;
+use crate::cross::CrossLeg;
+use crate::fallback::FallbackOracle;
+use crate::ratio::Ratio;
+use sep_40_oracle::Asset;
+use soroban_sdk::{contractevent, Address, Vec};
+
+/// The version of the bridge oracle event schema
+pub const EVENT_VERSION: u32 = 1;
+
+/// Emitted when the bridge oracle is initialized
+#[contractevent(topics = ["BridgeOracle", "init"])]
+#[derive(Clone)]
+pub struct Init {
+    pub version: u32,
+    pub admin: Address,
+    pub stellar_oracle: Address,
+    pub other_oracle: Address,
+}
+
+/// Emitted when an asset is mapped to the asset it is priced as
+#[contractevent(topics = ["BridgeOracle", "add_asset"])]
+#[derive(Clone)]
+pub struct AddAsset {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub to: Asset,
+}
+
+/// Emitted when an asset is mapped to an asset it is priced as the inverse of
+#[contractevent(topics = ["BridgeOracle", "add_inverse_asset"])]
+#[derive(Clone)]
+pub struct AddInverseAsset {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub to: Asset,
+}
+
+/// Emitted when the mapping of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_asset"])]
+#[derive(Clone)]
+pub struct RemoveAsset {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the ratio of an asset is set
+#[contractevent(topics = ["BridgeOracle", "set_ratio"])]
+#[derive(Clone)]
+pub struct SetRatio {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub ratio: Ratio,
+}
+
+/// Emitted when the ratio of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_ratio"])]
+#[derive(Clone)]
+pub struct RemoveRatio {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the admin is changed
+#[contractevent(topics = ["BridgeOracle", "set_admin"])]
+#[derive(Clone)]
+pub struct SetAdmin {
+    pub version: u32,
+    pub admin: Address,
+}
+
+/// Emitted when the max age of the price of an asset is set, where 0 removes it
+#[contractevent(topics = ["BridgeOracle", "set_max_age"])]
+#[derive(Clone)]
+pub struct SetMaxAge {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub max_age: u64,
+}
+
+/// Emitted when the price sources of an asset are set, where no sources removes them
+#[contractevent(topics = ["BridgeOracle", "set_price_sources"])]
+#[derive(Clone)]
+pub struct SetPriceSources {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub sources: Vec<Address>,
+    pub quorum: u32,
+    pub tolerance: i128,
+    pub max_age: u64,
+}
+
+/// Emitted when the fallback oracles of an asset are set
+#[contractevent(topics = ["BridgeOracle", "set_fallbacks"])]
+#[derive(Clone)]
+pub struct SetFallbacks {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub fallbacks: Vec<FallbackOracle>,
+}
+
+/// Emitted when the price of an asset is taken from a fallback oracle
+#[contractevent(topics = ["BridgeOracle", "fallback_used"])]
+#[derive(Clone)]
+pub struct FallbackUsed {
+    #[topic]
+    pub asset: Asset,
+    #[topic]
+    pub oracle: Address,
+    pub version: u32,
+}
+
+/// Emitted when the cross rate of an asset is set, where no legs removes it
+#[contractevent(topics = ["BridgeOracle", "set_cross_rate"])]
+#[derive(Clone)]
+pub struct SetCrossRate {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub legs: Vec<CrossLeg>,
+}
+
+/// Emitted when the fixed price of an asset is set
+#[contractevent(topics = ["BridgeOracle", "set_fixed_price"])]
+#[derive(Clone)]
+pub struct SetFixedPrice {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub price: i128,
+    pub decimals: u32,
+}
+
+/// Emitted when the fixed price of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_fixed_price"])]
+#[derive(Clone)]
+pub struct RemoveFixedPrice {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the guardian is changed
+#[contractevent(topics = ["BridgeOracle", "set_guardian"])]
+#[derive(Clone)]
+pub struct SetGuardian {
+    pub version: u32,
+    pub guardian: Address,
+}
+
+/// Emitted when the circuit breaker of an asset is set, where a max deviation of 0 removes it
+#[contractevent(topics = ["BridgeOracle", "set_breaker"])]
+#[derive(Clone)]
+pub struct SetBreaker {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub max_deviation: i128,
+    pub window: u64,
+}
+
+/// Emitted when a price is rejected for moving more than the max deviation of its asset
+#[contractevent(topics = ["BridgeOracle", "breaker_tripped"])]
+#[derive(Clone)]
+pub struct BreakerTripped {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub price: i128,
+    pub last_price: i128,
+}
+
+/// Emitted when the price bounds of an asset are set
+#[contractevent(topics = ["BridgeOracle", "set_bounds"])]
+#[derive(Clone)]
+pub struct SetBounds {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub min: i128,
+    pub max: i128,
+}
+
+/// Emitted when the price bounds of an asset are removed
+#[contractevent(topics = ["BridgeOracle", "remove_bounds"])]
+#[derive(Clone)]
+pub struct RemoveBounds {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when a price is rejected for being outside the bounds of the asset
+#[contractevent(topics = ["BridgeOracle", "price_out_of_bounds"])]
+#[derive(Clone)]
+pub struct PriceOutOfBounds {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub price: i128,
+    pub min: i128,
+    pub max: i128,
+}
+
+/// Emitted when an asset is frozen or unfrozen
+#[contractevent(topics = ["BridgeOracle", "set_frozen"])]
+#[derive(Clone)]
+pub struct SetFrozen {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub frozen: bool,
+}
+
+/// Emitted when the override band is set
+#[contractevent(topics = ["BridgeOracle", "set_override_band"])]
+#[derive(Clone)]
+pub struct SetOverrideBand {
+    pub version: u32,
+    pub band: i128,
+}
+
+/// Emitted when the guardian overrides the price of an asset
+#[contractevent(topics = ["BridgeOracle", "set_override"])]
+#[derive(Clone)]
+pub struct SetOverride {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub price: i128,
+    pub expires_at: u64,
+}
+
+/// Emitted when the override of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_override"])]
+#[derive(Clone)]
+pub struct RemoveOverride {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the price of an asset is served from its override
+#[contractevent(topics = ["BridgeOracle", "override_used"])]
+#[derive(Clone)]
+pub struct OverrideUsed {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub price: i128,
+}
+
+/// Emitted when the TWAP source of an asset is set
+#[contractevent(topics = ["BridgeOracle", "set_twap"])]
+#[derive(Clone)]
+pub struct SetTwap {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+    pub pair: Address,
+    pub window: u64,
+    pub min_liquidity: i128,
+}
+
+/// Emitted when the TWAP source of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_twap"])]
+#[derive(Clone)]
+pub struct RemoveTwap {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the default oracles are rotated
+#[contractevent(topics = ["BridgeOracle", "set_oracles"])]
+#[derive(Clone)]
+pub struct SetOracles {
+    pub version: u32,
+    pub stellar_oracle: Address,
+    pub other_oracle: Address,
+}
+
+/// Emitted when an asset is routed to an oracle
+#[contractevent(topics = ["BridgeOracle", "set_route"])]
+#[derive(Clone)]
+pub struct SetRoute {
+    #[topic]
+    pub asset: Asset,
+    #[topic]
+    pub oracle: Address,
+    pub version: u32,
+}
+
+/// Emitted when the route of an asset is removed
+#[contractevent(topics = ["BridgeOracle", "remove_route"])]
+#[derive(Clone)]
+pub struct RemoveRoute {
+    #[topic]
+    pub asset: Asset,
+    pub version: u32,
+}
+
+/// Emitted when the shares of a Comet pool are priced at fair value
+#[contractevent(topics = ["BridgeOracle", "set_comet_pool"])]
+#[derive(Clone)]
+pub struct SetCometPool {
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+}
+
+/// Emitted when the shares of a Comet pool are no longer priced at fair value
+#[contractevent(topics = ["BridgeOracle", "remove_comet_pool"])]
+#[derive(Clone)]
+pub struct RemoveCometPool {
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+}
+
+/// Emitted when the price cache is enabled or disabled
+#[contractevent(topics = ["BridgeOracle", "set_cache"])]
+#[derive(Clone)]
+pub struct SetCache {
+    pub version: u32,
+    pub enabled: bool,
+}
+
+/// Emitted when the adapter of an oracle is set
+#[contractevent(topics = ["BridgeOracle", "set_adapter"])]
+#[derive(Clone)]
+pub struct SetAdapter {
+    #[topic]
+    pub oracle: Address,
+    pub version: u32,
+    pub adapter: Adapter,
+}
+
+/// Emitted when the number of observations kept for each asset is set
+#[contractevent(topics = ["BridgeOracle", "set_history_size"])]
+#[derive(Clone)]
+pub struct SetHistorySize {
+    pub version: u32,
+    pub size: u32,
+}
@@ -0,0 +1,50 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Address, Env};
+use crate::events::{self, EVENT_VERSION};
+use crate::{adapter, price, storage};
+
+/// An oracle used when the price of an asset is missing or stale
+#[derive(Clone)]
+#[contracttype]
+pub struct FallbackOracle {
+    /// The oracle quoting the bridge asset of the asset
+    pub oracle: Address,
+    /// The max age of a price from the oracle in seconds, or 0 for no max age
+    pub max_age: u64,
+}
+
+/// Fetch the first fresh price of the asset from its fallback oracles
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+pub fn fetch_price(env: &Env, asset: &Asset) -> Option<(PriceData, Address)> {
+    let fallbacks = storage::get_fallbacks(env, asset);
+    if fallbacks.is_empty() {
+        return None;
+    }
+    let to_asset = storage::get_bridge_asset(env, asset);
+    let decimals = price::decimals(env);
+    let now = env.ledger().timestamp();
+
+    for fallback in fallbacks.iter() {
+        let price = match adapter::lastprice(env, &fallback.oracle, &to_asset) {
+            Some(price) => price,
+            None => continue,
+        };
+        if fallback.max_age != 0 && price.timestamp < now.saturating_sub(fallback.max_age) {
+            continue;
+        }
+        let oracle_decimals = match price::oracle_decimals(env, &fallback.oracle) {
+            Some(oracle_decimals) => oracle_decimals,
+            None => continue,
+        };
+
+        events::FallbackUsed { version: EVENT_VERSION, asset: asset.clone(), oracle: fallback.oracle.clone() }
+            .publish(env);
+        let price = PriceData {
+            price: price::rescale(price.price, oracle_decimals, decimals),
+            timestamp: price.timestamp,
+        };
+        return Some((price, fallback.oracle));
+    }
+    None
+}
@@ -0,0 +1,109 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Env, Vec};
+use crate::storage;
+
+/// The max number of observations kept for an asset
+pub const MAX_HISTORY_SIZE: u32 = 256;
+
+/// The last observations of the price of an asset, as a ring buffer
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceHistory {
+    /// The index the next observation is written to once the buffer is full, which is the
+    /// index of the oldest observation
+    pub next: u32,
+    /// The observations, oldest first from `next`
+    pub observations: Vec<PriceData>,
+}
+
+/// Record an observation of the price of an asset, overwriting the oldest observation once the
+/// history is full. Observations that are not newer than the last one are ignored
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The price to record
+///
+/// Returns true if the observation was recorded
+pub fn record(env: &Env, asset: &Asset, price: &PriceData) -> bool {
+    let size = storage::get_history_size(env);
+    if size == 0 {
+        return false;
+    }
+    let mut history = storage::get_history(env, asset)
+        .unwrap_or(PriceHistory { next: 0, observations: Vec::new(env) });
+    if let Some(latest) = latest(&history) {
+        if price.timestamp <= latest.timestamp {
+            return false;
+        }
+    }
+
+    let len = history.observations.len();
+    if len > size || (len < size && history.next != len) {
+        // the size changed after the buffer filled up, so restart it in order
+        let mut observations = Vec::new(env);
+        for index in len.saturating_sub(size)..len {
+            observations.push_back(history.observations.get_unchecked((history.next + index) % len));
+        }
+        let next = observations.len() % size;
+        history = PriceHistory { next, observations };
+    }
+    if history.observations.len() < size {
+        history.observations.push_back(price.clone());
+        history.next = history.observations.len() % size;
+    } else {
+        history.observations.set(history.next, price.clone());
+        history.next = (history.next + 1) % size;
+    }
+    storage::set_history(env, asset, &history);
+    true
+}
+
+/// The newest observation of a history
+fn latest(history: &PriceHistory) -> Option<PriceData> {
+    let len = history.observations.len();
+    if len == 0 {
+        return None;
+    }
+    history.observations.get((history.next + len - 1) % len)
+}
+
+/// Fetch the last observations of the price of an asset, newest first
+/// # Arguments
+/// * `asset` - The asset to fetch the observations for
+/// * `records` - The max number of observations to fetch
+pub fn prices(env: &Env, asset: &Asset, records: u32) -> Option<Vec<PriceData>> {
+    let history = storage::get_history(env, asset)?;
+    let len = history.observations.len();
+    if len == 0 {
+        return None;
+    }
+    let mut prices = Vec::new(env);
+    for index in 0..records.min(len) {
+        prices.push_back(history.observations.get_unchecked((history.next + len - 1 - index) % len));
+    }
+    Some(prices)
+}
+
+/// Fetch the time-weighted average of the last observations of the price of an asset, where each
+/// observation is weighted by the time until the next one, and the newest until now
+/// # Arguments
+/// * `asset` - The asset to fetch the average for
+/// * `records` - The max number of observations to average
+///
+/// Returns the average with the timestamp of the newest observation
+pub fn twap(env: &Env, asset: &Asset, records: u32) -> Option<PriceData> {
+    let prices = prices(env, asset, records)?;
+    let newest = prices.first()?;
+    let mut end = env.ledger().timestamp();
+    let mut weighted: i128 = 0;
+    let mut duration: u64 = 0;
+    for price in prices.iter() {
+        let elapsed = end.saturating_sub(price.timestamp);
+        weighted += price.price * elapsed as i128;
+        duration += elapsed;
+        end = price.timestamp;
+    }
+    if duration == 0 {
+        return Some(newest);
+    }
+    Some(PriceData { price: weighted / duration as i128, timestamp: newest.timestamp })
+}
@@ -1,11 +1,41 @@
 #![no_std]
+#![allow(clippy::upper_case_acronyms)]
 #[cfg(any(test, feature = "testutils"))]
 extern crate std;
 
+mod adapter;
+mod aggregate;
+mod bounds;
+mod breaker;
+mod comet;
+mod constants;
 mod contract;
+mod cross;
+mod errors;
+pub mod events;
+mod fallback;
+mod history;
+mod mapping;
+mod overrides;
+mod price;
+mod ratio;
+mod reflector;
 mod storage;
+mod twap;
 #[cfg(test)]
 mod test;
 
+pub use adapter::Adapter;
+pub use aggregate::{AggregatePrice, PriceSources};
+pub use bounds::PriceBounds;
+pub use breaker::Breaker;
 pub use contract::*;
+pub use cross::CrossLeg;
+pub use errors::BridgeOracleError;
+pub use fallback::FallbackOracle;
+pub use mapping::Mapping;
+pub use overrides::PriceOverride;
+pub use price::{DetailedPrice, FixedPrice, PriceSource, PriceStatus};
+pub use ratio::{Rate, Ratio};
+pub use twap::{Observation, TwapConfig, TwapState};
 
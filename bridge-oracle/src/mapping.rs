@@ -0,0 +1,146 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, vec, Address, Env, Vec};
+use crate::{price, storage};
+
+/// A mapping of an asset to the asset it is priced as
+#[derive(Clone)]
+#[contracttype]
+pub struct Mapping {
+    /// The mapped asset
+    pub asset: Asset,
+    /// The asset it is priced as
+    pub to: Asset,
+    /// If the asset is priced as the inverse of the price of the asset it is mapped to
+    pub inverse: bool,
+}
+
+/// Check an asset can be mapped to another asset. The mapping must not point to the asset
+/// itself, must not close a cycle of mappings, and the asset it points to must be quoted
+/// by the oracles it is priced by
+/// # Arguments
+/// * `asset` - The asset to map
+/// * `to` - The asset it is priced as
+pub fn is_valid(env: &Env, asset: &Asset, to: &Asset) -> bool {
+    // assets have no `PartialEq`, so they are compared by their encoding
+    if asset.clone().to_xdr(env) == to.clone().to_xdr(env) || closes_cycle(env, asset, to) {
+        return false;
+    }
+    is_quoted(env, to)
+}
+
+/// Map an asset to the asset it is priced as, replacing any previous mapping
+/// # Arguments
+/// * `asset` - The asset to map
+/// * `to` - The asset it is priced as
+/// * `inverse` - If the asset is priced as the inverse of the price of `to`
+pub fn add(env: &Env, asset: &Asset, to: &Asset, inverse: bool) {
+    storage::set_bridge_asset(env, asset, to);
+    if inverse {
+        storage::set_inverse(env, asset);
+    } else {
+        storage::remove_inverse(env, asset);
+    }
+    if storage::get_mapping_index(env, asset).is_none() {
+        let count = storage::get_mapping_count(env);
+        storage::set_mapped_asset(env, count, asset);
+        storage::set_mapping_index(env, asset, count);
+        storage::set_mapping_count(env, count + 1);
+    }
+    price::sync_listed(env, asset);
+}
+
+/// Remove the mapping of an asset, moving the last mapping into its position
+/// # Arguments
+/// * `asset` - The asset to remove the mapping for
+pub fn remove(env: &Env, asset: &Asset) {
+    storage::remove_bridge_asset(env, asset);
+    storage::remove_inverse(env, asset);
+    price::sync_listed(env, asset);
+    let index = match storage::get_mapping_index(env, asset) {
+        Some(index) => index,
+        None => return,
+    };
+    let last = storage::get_mapping_count(env) - 1;
+    if index != last {
+        if let Some(moved) = storage::get_mapped_asset(env, last) {
+            storage::set_mapped_asset(env, index, &moved);
+            storage::set_mapping_index(env, &moved, index);
+        }
+    }
+    storage::remove_mapped_asset(env, last);
+    storage::remove_mapping_index(env, asset);
+    storage::set_mapping_count(env, last);
+}
+
+/// Invert a price of the asset an asset is mapped to, if the mapping of the asset is inverse,
+/// as `1 / price` in the decimals of the bridge oracle, rounded down
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The price of the asset it is mapped to
+///
+/// Returns None if the price to invert is not positive
+pub fn invert(env: &Env, asset: &Asset, price: PriceData) -> Option<PriceData> {
+    if !storage::get_inverse(env, asset) {
+        return Some(price);
+    }
+    if price.price <= 0 {
+        return None;
+    }
+    let scalar = 10i128.pow(price::decimals(env));
+    Some(PriceData { price: scalar.fixed_div_floor(env, &price.price, &scalar), timestamp: price.timestamp })
+}
+
+/// Check following the mappings from `to` leads back to `asset`
+fn closes_cycle(env: &Env, asset: &Asset, to: &Asset) -> bool {
+    let asset = asset.clone().to_xdr(env);
+    let mut next = to.clone();
+    // a chain can't be longer than the number of mappings
+    for _ in 0..=storage::get_mapping_count(env) {
+        match storage::get_mapping(env, &next) {
+            Some(mapped) if mapped.clone().to_xdr(env) == asset => return true,
+            Some(mapped) => next = mapped,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Check the asset is quoted by the oracles it is priced by, without following its mapping
+fn is_quoted(env: &Env, asset: &Asset) -> bool {
+    if storage::get_fixed_price(env, asset).is_some() {
+        return true;
+    }
+    if let Some(sources) = storage::get_price_sources(env, asset) {
+        let quoted = sources.sources.iter().filter(|oracle| lists(env, oracle, asset)).count();
+        return quoted as u32 >= sources.quorum;
+    }
+    lists(env, &price::oracle(env, asset), asset)
+}
+
+/// Check the oracle lists the asset
+fn lists(env: &Env, oracle: &Address, asset: &Asset) -> bool {
+    price::try_invoke::<Vec<Asset>>(env, oracle, "assets", vec![env])
+        .is_some_and(|assets| assets.contains(asset))
+}
+
+/// Fetch a page of the mappings
+/// # Arguments
+/// * `start` - The index of the first mapping
+/// * `limit` - The max number of mappings
+pub fn page(env: &Env, start: u32, limit: u32) -> Vec<Mapping> {
+    let end = start.saturating_add(limit).min(storage::get_mapping_count(env));
+    let mut mappings = Vec::new(env);
+    for index in start..end {
+        let asset = match storage::get_mapped_asset(env, index) {
+            Some(asset) => asset,
+            None => continue,
+        };
+        if let Some(to) = storage::get_mapping(env, &asset) {
+            let inverse = storage::get_inverse(env, &asset);
+            mappings.push_back(Mapping { asset, to, inverse });
+        }
+    }
+    mappings
+}
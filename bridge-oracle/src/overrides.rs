@@ -0,0 +1,43 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contracttype, Env};
+use crate::constants::SCALAR_7;
+use crate::events::{self, EVENT_VERSION};
+use crate::storage;
+
+/// A price of an asset set by the guardian
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceOverride {
+    /// The price of the asset, in the decimals of the bridge oracle
+    pub price: i128,
+    /// The timestamp the override expires at
+    pub expires_at: u64,
+}
+
+/// Fetch the override of an asset if it has not expired, publishing an event when it is used
+/// # Arguments
+/// * `asset` - The asset to fetch the override for
+pub fn active(env: &Env, asset: &Asset) -> Option<PriceData> {
+    let now = env.ledger().timestamp();
+    let price_override = storage::get_override(env, asset).filter(|o| o.expires_at > now)?;
+    events::OverrideUsed { version: EVENT_VERSION, asset: asset.clone(), price: price_override.price }.publish(env);
+    Some(PriceData { price: price_override.price, timestamp: now })
+}
+
+/// Check an override price is within the override band around the last accepted price of the
+/// asset. The band is anchored to the stored price rather than the live feed, so a feed moved
+/// within the ledger can't widen what the guardian may set
+/// # Arguments
+/// * `asset` - The asset the override is for
+/// * `price` - The override price
+pub fn is_within_band(env: &Env, asset: &Asset, price: i128) -> bool {
+    let band = match storage::get_override_band(env) {
+        Some(band) => band,
+        None => return false,
+    };
+    let anchor = match storage::get_last_good_price(env, asset) {
+        Some(last) => last,
+        None => return false,
+    };
+    (price - anchor.price).abs() * SCALAR_7 <= band * anchor.price
+}
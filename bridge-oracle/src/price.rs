@@ -0,0 +1,594 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contracttype, panic_with_error, vec, Address, Env, Error, Symbol, TryFromVal, Val, Vec};
+use crate::aggregate::{self, AggregatePrice, PriceSources};
+use crate::cross::{self, CrossLeg};
+use crate::errors::BridgeOracleError;
+use crate::twap::{self, TwapConfig};
+use crate::{adapter, bounds, breaker, comet, fallback, history, mapping, overrides, ratio, storage};
+
+/// A fixed price of an asset
+#[derive(Clone)]
+#[contracttype]
+pub struct FixedPrice {
+    /// The price of the asset
+    pub price: i128,
+    /// The decimals of the price
+    pub decimals: u32,
+}
+
+/// Where the price of an asset comes from
+#[derive(Clone)]
+enum Route {
+    /// The asset is priced by an oracle
+    Oracle(Address, Asset),
+    /// The asset is priced by the median of multiple oracles
+    Median(PriceSources, Asset),
+    /// The asset has a fixed price, in the decimals of the bridge oracle
+    Fixed(i128),
+    /// The asset is priced as a product or quotient of the prices of other assets
+    Cross(Vec<CrossLeg>),
+    /// The asset is priced by its time-weighted average price in a Soroswap pair
+    Twap(TwapConfig, Asset),
+    /// The asset is a share of a Comet pool, priced at the fair value of the pool
+    Comet(Address),
+}
+
+/// Find where the price of the asset comes from
+fn route(env: &Env, asset: &Asset) -> Route {
+    if let Some(legs) = storage::get_cross_rate(env, asset) {
+        return Route::Cross(legs);
+    }
+    feed_route(env, asset)
+}
+
+/// List the asset if it is priced by a mapping, cross rate, TWAP or Comet pool of its own, or
+/// unlist it if it has none. Routes, fixed prices and price sources price the asset an oracle
+/// quotes, which is listed through the assets mapped to it
+/// # Arguments
+/// * `asset` - The asset to list or unlist
+pub fn sync_listed(env: &Env, asset: &Asset) {
+    let priced = storage::get_mapping(env, asset).is_some()
+        || storage::get_cross_rate(env, asset).is_some()
+        || storage::get_twap(env, asset).is_some()
+        || matches!(asset, Asset::Stellar(pool) if storage::get_comet_pool(env, pool));
+    let mut assets = storage::get_assets(env);
+    match (priced, assets.first_index_of(asset)) {
+        (true, None) => assets.push_back(asset.clone()),
+        (false, Some(index)) => {
+            assets.remove(index);
+        }
+        _ => return,
+    }
+    storage::set_assets(env, &assets);
+}
+
+/// Find the feed the asset is priced by
+fn feed_route(env: &Env, asset: &Asset) -> Route {
+    if let Some(config) = storage::get_twap(env, asset) {
+        return Route::Twap(config, asset.clone());
+    }
+    if let Asset::Stellar(pool) = asset {
+        if storage::get_comet_pool(env, pool) {
+            return Route::Comet(pool.clone());
+        }
+    }
+    bridge_route(env, asset)
+}
+
+/// Find the oracle the bridge asset of the asset is priced by
+fn bridge_route(env: &Env, asset: &Asset) -> Route {
+    let to_asset = storage::get_bridge_asset(env, asset);
+    if let Some(sources) = storage::get_price_sources(env, &to_asset) {
+        return Route::Median(sources, to_asset);
+    }
+
+    if let Some(fixed) = storage::get_fixed_price(env, &to_asset) {
+        return Route::Fixed(rescale(fixed.price, fixed.decimals, decimals(env)));
+    }
+
+    Route::Oracle(oracle(env, &to_asset), to_asset)
+}
+
+/// Find the oracle an asset is quoted by. Assets without a route use the default route of their
+/// variant, the stellar oracle for stellar assets and the other oracle for other assets
+/// # Arguments
+/// * `asset` - The asset quoted by the oracle
+pub fn oracle(env: &Env, asset: &Asset) -> Address {
+    if let Some(oracle) = storage::get_route(env, asset) {
+        return oracle;
+    }
+    match asset {
+        Asset::Stellar(_) => storage::get_stellar_oracle(env),
+        Asset::Other(_) => storage::get_other_oracle(env),
+    }
+}
+
+/// Find the kind of source a route prices an asset from
+fn route_source(env: &Env, route: &Route) -> PriceSource {
+    match route {
+        Route::Oracle(oracle, _) if *oracle == storage::get_stellar_oracle(env) => PriceSource::StellarOracle,
+        Route::Oracle(oracle, _) if *oracle == storage::get_other_oracle(env) => PriceSource::OtherOracle,
+        Route::Oracle(_, _) => PriceSource::RoutedOracle,
+        Route::Median(_, _) => PriceSource::Median,
+        Route::Fixed(_) => PriceSource::Fixed,
+        Route::Cross(_) => PriceSource::Cross,
+        Route::Twap(_, _) => PriceSource::Twap,
+        Route::Comet(_) => PriceSource::Comet,
+    }
+}
+
+/// Fetch the last price from a route, the oracles it came from and the spread between them. A
+/// price composed of other prices has the largest spread of its parts
+fn route_lastprice(env: &Env, route: Route) -> Option<AggregatePrice> {
+    match route {
+        Route::Oracle(oracle, to_asset) => {
+            let price = adapter::lastprice(env, &oracle, &to_asset)?;
+            let (from, to) = scale(env, &oracle)?;
+            Some(AggregatePrice { price: rescale_price(price, from, to), sources: vec![env, oracle], spread: 0 })
+        }
+        Route::Median(sources, to_asset) => aggregate::lastprice(env, &sources, &to_asset),
+        Route::Fixed(price) => Some(AggregatePrice {
+            price: PriceData { price, timestamp: env.ledger().timestamp() },
+            sources: Vec::new(env),
+            spread: 0,
+        }),
+        Route::Cross(legs) => {
+            let mut prices = Vec::new(env);
+            let mut sources: Vec<Address> = Vec::new(env);
+            let mut spread = 0;
+            for leg in legs.iter() {
+                let leg_price = leg_lastprice(env, &leg.asset)?;
+                prices.push_back(leg_price.price);
+                spread = spread.max(leg_price.spread);
+                for source in leg_price.sources.iter() {
+                    if !sources.contains(&source) {
+                        sources.push_back(source);
+                    }
+                }
+            }
+            cross::compose(env, &legs, &prices).map(|price| AggregatePrice { price, sources, spread })
+        }
+        Route::Twap(config, asset) => {
+            let (quote, twap) = twap::lastprice(env, &asset, &config)?;
+            let quote_price = route_lastprice(env, bridge_route(env, &Asset::Stellar(quote)))?;
+            let scalar = 10i128.pow(decimals(env));
+            let price = twap.fixed_mul_floor(env, &quote_price.price.price, &scalar);
+            let mut sources = quote_price.sources;
+            sources.push_front(config.pair);
+            Some(AggregatePrice {
+                price: PriceData { price, timestamp: quote_price.price.timestamp.min(env.ledger().timestamp()) },
+                sources,
+                spread: quote_price.spread,
+            })
+        }
+        Route::Comet(pool) => comet::lastprice(env, &pool),
+    }
+}
+
+/// Fetch the last price of a leg of a cross rate from its feed, adjusted by the mapping of the
+/// leg's asset like the asset's own price
+fn leg_lastprice(env: &Env, asset: &Asset) -> Option<AggregatePrice> {
+    let route = feed_route(env, asset);
+    let bridged = is_bridged(&route);
+    let last = route_lastprice(env, route)?;
+    Some(AggregatePrice { price: adjust(env, asset, bridged, last.price)?, ..last })
+}
+
+/// Fetch the price from a route at a timestamp
+fn route_price(env: &Env, route: Route, timestamp: u64) -> Option<PriceData> {
+    match route {
+        Route::Oracle(oracle, to_asset) => {
+            let price = adapter::price(env, &oracle, &to_asset, timestamp)?;
+            let (from, to) = scale(env, &oracle)?;
+            Some(rescale_price(price, from, to))
+        }
+        Route::Median(sources, to_asset) => aggregate::price(env, &sources, &to_asset, timestamp).map(|p| p.price),
+        Route::Fixed(price) => Some(PriceData { price, timestamp: timestamp - timestamp % resolution(env) as u64 }),
+        Route::Cross(legs) => {
+            let mut prices = Vec::new(env);
+            for leg in legs.iter() {
+                let route = feed_route(env, &leg.asset);
+                let bridged = is_bridged(&route);
+                prices.push_back(adjust(env, &leg.asset, bridged, route_price(env, route, timestamp)?)?);
+            }
+            cross::compose(env, &legs, &prices)
+        }
+        // TWAP sources only have observations from the current ledger onwards
+        Route::Twap(_, _) => None,
+        // pool balances are only known at the current ledger
+        Route::Comet(_) => None,
+    }
+}
+
+/// Invoke a function on an oracle, returning None if the oracle fails
+/// # Arguments
+/// * `oracle` - The oracle to invoke
+/// * `function` - The function to invoke
+/// * `args` - The arguments of the function
+pub fn try_invoke<T: TryFromVal<Env, Val>>(env: &Env, oracle: &Address, function: &str, args: Vec<Val>) -> Option<T> {
+    match env.try_invoke_contract::<T, Error>(oracle, &Symbol::new(env, function), args) {
+        Ok(Ok(result)) => Some(result),
+        _ => None,
+    }
+}
+
+/// The state of the last price of an asset
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum PriceStatus {
+    /// The price can be used
+    Valid,
+    /// The price is older than the max age of the asset
+    Stale,
+    /// The price moved more than the max deviation of the asset
+    Deviated,
+    /// The price is outside the bounds of the asset
+    OutOfBounds,
+}
+
+/// The kind of source the last price of an asset came from
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum PriceSource {
+    /// The oracle for stellar assets
+    StellarOracle,
+    /// The oracle for other assets
+    OtherOracle,
+    /// An oracle the asset is routed to
+    RoutedOracle,
+    /// The median of the price sources of the asset
+    Median,
+    /// A fixed price, such as the price of USD, the base of the oracles
+    Fixed,
+    /// A cross rate of the prices of other assets
+    Cross,
+    /// The time-weighted average price of a Soroswap pair
+    Twap,
+    /// The fair value of a share of a Comet pool
+    Comet,
+    /// A fallback oracle of the asset
+    Fallback,
+    /// A guardian override
+    Override,
+    /// The last accepted price of a frozen asset
+    Frozen,
+}
+
+/// The last price of an asset
+#[derive(Clone)]
+#[contracttype]
+pub struct LastPrice {
+    pub price: PriceData,
+    /// The oracles the price came from
+    pub sources: Vec<Address>,
+    pub status: PriceStatus,
+    pub source: PriceSource,
+    /// The spread between the prices of the sources, relative to the price with 7 decimals
+    pub spread: i128,
+}
+
+/// The last price of an asset with the metadata of where it came from
+#[derive(Clone)]
+#[contracttype]
+pub struct DetailedPrice {
+    pub price: PriceData,
+    pub status: PriceStatus,
+    /// The kind of source the price came from
+    pub source: PriceSource,
+    /// The seconds since the price was observed upstream
+    pub age: u64,
+    /// The number of oracles the price was aggregated from
+    pub count: u32,
+    /// The spread between the prices of the oracles, relative to the price with 7 decimals
+    pub spread: i128,
+}
+
+/// Fetch the last price of the asset, which is its override while one is active
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+pub fn fetch_last(env: &Env, asset: &Asset) -> Option<LastPrice> {
+    if let Some(price) = overrides::active(env, asset) {
+        return Some(LastPrice {
+            price,
+            sources: Vec::new(env),
+            status: PriceStatus::Valid,
+            source: PriceSource::Override,
+            spread: 0,
+        });
+    }
+    fetch_feed_last(env, asset)
+}
+
+/// Fetch the last price of the asset with the metadata of where it came from, whatever its status
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+pub fn fetch_detailed(env: &Env, asset: &Asset) -> Option<DetailedPrice> {
+    let last = fetch_last(env, asset)?;
+    Some(DetailedPrice {
+        age: env.ledger().timestamp().saturating_sub(last.price.timestamp),
+        count: last.sources.len(),
+        price: last.price,
+        status: last.status,
+        source: last.source,
+        spread: last.spread,
+    })
+}
+
+/// Fetch the last price of the asset from the oracle its bridge asset is priced by, using the
+/// fallback oracles of the asset if the price is missing or stale. A frozen asset returns its
+/// last accepted price. Overrides are ignored. While the cache is enabled, a price resolved by a
+/// poke in the current ledger is used
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+pub fn fetch_feed_last(env: &Env, asset: &Asset) -> Option<LastPrice> {
+    if storage::get_frozen(env, asset) {
+        return storage::get_last_good_price(env, asset).map(|price| LastPrice {
+            price,
+            sources: Vec::new(env),
+            status: PriceStatus::Valid,
+            source: PriceSource::Frozen,
+            spread: 0,
+        });
+    }
+    if storage::get_cache(env) {
+        if let Some(last) = storage::get_cached_price(env, asset, env.ledger().sequence()) {
+            return Some(last);
+        }
+    }
+    resolve_feed_last(env, asset)
+}
+
+/// Resolve the last price of the asset from its route and fallback oracles
+fn resolve_feed_last(env: &Env, asset: &Asset) -> Option<LastPrice> {
+    let route = route(env, asset);
+    let source = route_source(env, &route);
+    let bridged = is_bridged(&route);
+    let primary = route_lastprice(env, route)
+        .and_then(|last| Some(AggregatePrice { price: adjust(env, asset, bridged, last.price)?, ..last }));
+    let fresh = match primary.clone() {
+        Some(last) if !is_stale(env, asset, &last.price) => Some((last, source)),
+        _ => fallback::fetch_price(env, asset).and_then(|(price, oracle)| {
+            // fallback oracles quote the bridge asset
+            let price = adjust(env, asset, true, price)?;
+            Some((AggregatePrice { price, sources: vec![env, oracle], spread: 0 }, PriceSource::Fallback))
+        }),
+    };
+    match fresh {
+        Some((last, source)) => {
+            let status = if !bounds::check(env, asset, &last.price) {
+                PriceStatus::OutOfBounds
+            } else if breaker::check(env, asset, &last.price) {
+                PriceStatus::Valid
+            } else {
+                PriceStatus::Deviated
+            };
+            Some(LastPrice { price: last.price, sources: last.sources, status, source, spread: last.spread })
+        }
+        None => primary.map(|last| LastPrice {
+            price: last.price,
+            sources: last.sources,
+            status: PriceStatus::Stale,
+            source,
+            spread: last.spread,
+        }),
+    }
+}
+
+/// Observe the pair of the asset if it has a TWAP source, then resolve its last price from its
+/// route and fallback oracles, recording it as the last accepted price and in its history if it
+/// is valid. While the cache is enabled, the price is kept for the rest of the ledger. Frozen
+/// assets and overrides are left untouched
+/// # Arguments
+/// * `asset` - The asset to record the price for
+pub fn record(env: &Env, asset: &Asset) {
+    if let Some(config) = storage::get_twap(env, asset) {
+        twap::observe(env, asset, &config);
+    }
+    if storage::get_frozen(env, asset) {
+        return;
+    }
+    if let Some(last) = resolve_feed_last(env, asset) {
+        if last.status == PriceStatus::Valid {
+            breaker::record(env, asset, &last.price);
+            history::record(env, asset, &last.price);
+        }
+        if storage::get_cache(env) {
+            storage::set_cached_price(env, asset, env.ledger().sequence(), &last);
+        }
+    }
+}
+
+/// Fetch the sources that took part in the last price of the asset
+/// # Arguments
+/// * `asset` - The asset to fetch the sources for
+pub fn fetch_sources(env: &Env, asset: &Asset) -> Vec<Address> {
+    match fetch_last(env, asset) {
+        Some(last) if last.status == PriceStatus::Valid => last.sources,
+        _ => Vec::new(env),
+    }
+}
+
+/// Fetch the price of the asset at a timestamp from the oracle its bridge asset is priced by
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+/// * `timestamp` - The timestamp to fetch the price at
+pub fn fetch_price_at(env: &Env, asset: &Asset, timestamp: u64) -> Option<PriceData> {
+    let route = route(env, asset);
+    let bridged = is_bridged(&route);
+    adjust(env, asset, bridged, route_price(env, route, timestamp)?)
+}
+
+/// Fetch the last prices of the asset from the oracle its bridge asset is priced by, or from the
+/// history of the asset if the oracle has none
+/// # Arguments
+/// * `asset` - The asset to fetch the prices for
+/// * `records` - The number of records to fetch
+pub fn fetch_prices(env: &Env, asset: &Asset, records: u32) -> Option<Vec<PriceData>> {
+    let route = route(env, asset);
+    let bridged = is_bridged(&route);
+    let prices = match route_prices(env, route, records) {
+        Some(prices) if !prices.is_empty() => prices,
+        // recorded prices are already adjusted
+        _ => return history::prices(env, asset, records),
+    };
+    if storage::get_ratio(env, asset).is_none() && !(bridged && storage::get_inverse(env, asset)) {
+        return Some(prices);
+    }
+    let mut scaled = Vec::new(env);
+    for price in prices.iter() {
+        scaled.push_back(adjust(env, asset, bridged, price)?);
+    }
+    Some(scaled)
+}
+
+/// Check a route prices the bridge asset of an asset, rather than the asset itself
+fn is_bridged(route: &Route) -> bool {
+    matches!(route, Route::Oracle(_, _) | Route::Median(_, _) | Route::Fixed(_))
+}
+
+/// Adjust a price from the route of an asset to the price of the asset, inverting prices of its
+/// bridge asset if it has an inverse mapping and scaling them by the ratio of the asset
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `bridged` - If the price is a price of the bridge asset of the asset
+/// * `price` - The price to adjust
+fn adjust(env: &Env, asset: &Asset, bridged: bool, price: PriceData) -> Option<PriceData> {
+    let price = if bridged { mapping::invert(env, asset, price)? } else { price };
+    ratio::apply(env, asset, price)
+}
+
+/// Fetch the last prices from a route, before the asset is adjusted
+fn route_prices(env: &Env, route: Route, records: u32) -> Option<Vec<PriceData>> {
+    match route {
+        Route::Oracle(oracle, to_asset) => {
+            let prices = adapter::prices(env, &oracle, &to_asset, records)?;
+            let (from, to) = scale(env, &oracle)?;
+            let mut rescaled = Vec::new(env);
+            for price in prices.iter() {
+                rescaled.push_back(rescale_price(price, from, to));
+            }
+            Some(rescaled)
+        }
+        Route::Fixed(price) => {
+            // fixed prices are at the same timestamps as aggregated prices
+            let resolution = resolution(env) as u64;
+            let now = env.ledger().timestamp();
+            let mut prices = Vec::new(env);
+            for i in 0..records as u64 {
+                match (now - now % resolution).checked_sub(i * resolution) {
+                    Some(timestamp) => prices.push_back(PriceData { price, timestamp }),
+                    None => break,
+                }
+            }
+            Some(prices)
+        }
+        route => {
+            // aggregated prices are built from the prices of each source at the same timestamps
+            let resolution = resolution(env) as u64;
+            let now = env.ledger().timestamp();
+            let mut prices = Vec::new(env);
+            for i in 0..records as u64 {
+                let timestamp = match (now - now % resolution).checked_sub(i * resolution) {
+                    Some(timestamp) => timestamp,
+                    None => break,
+                };
+                if let Some(price) = route_price(env, route.clone(), timestamp) {
+                    prices.push_back(price);
+                }
+            }
+            Some(prices)
+        }
+    }
+}
+
+/// Fetch the decimals of the bridge oracle, which are the decimals of the stellar oracle
+pub fn decimals(env: &Env) -> u32 {
+    oracle_decimals(env, &storage::get_stellar_oracle(env)).unwrap_or_else(|| {
+        panic_with_error!(env, BridgeOracleError::PriceNotFoundError);
+    })
+}
+
+/// Fetch the decimals of an oracle, as stored when the oracle was configured, or from the
+/// oracle itself if they were not stored
+/// # Arguments
+/// * `oracle` - The oracle to fetch the decimals for
+pub fn oracle_decimals(env: &Env, oracle: &Address) -> Option<u32> {
+    storage::get_decimals(env, oracle).or_else(|| try_invoke::<u32>(env, oracle, "decimals", vec![env]))
+}
+
+/// Store the decimals of an oracle, so its prices are rescaled without calling it for its
+/// decimals. Oracles that don't return their decimals are skipped
+/// # Arguments
+/// * `oracle` - The oracle to store the decimals for
+pub fn store_decimals(env: &Env, oracle: &Address) {
+    if let Some(decimals) = try_invoke::<u32>(env, oracle, "decimals", vec![env]) {
+        storage::set_decimals(env, oracle, decimals);
+    }
+}
+
+/// Fetch the decimals prices from an oracle are quoted in, and the decimals of the bridge oracle
+fn scale(env: &Env, oracle: &Address) -> Option<(u32, u32)> {
+    Some((oracle_decimals(env, oracle)?, decimals(env)))
+}
+
+fn rescale_price(price: PriceData, from: u32, to: u32) -> PriceData {
+    PriceData { price: rescale(price.price, from, to), timestamp: price.timestamp }
+}
+
+/// Rescale a price from `from` decimals to `to` decimals, rounding half away from zero
+/// # Arguments
+/// * `price` - The price to rescale
+/// * `from` - The decimals of the price
+/// * `to` - The decimals to rescale the price to
+pub fn rescale(price: i128, from: u32, to: u32) -> i128 {
+    if from == to {
+        price
+    } else if from < to {
+        price * 10i128.pow(to - from)
+    } else {
+        let factor = 10i128.pow(from - to);
+        let half = if price < 0 { -factor / 2 } else { factor / 2 };
+        (price + half) / factor
+    }
+}
+
+/// Rescale a price between decimals, or none if it doesn't fit in an i128
+pub fn checked_rescale(price: i128, from: u32, to: u32) -> Option<i128> {
+    if from < to {
+        price.checked_mul(10i128.checked_pow(to - from)?)
+    } else {
+        10i128.checked_pow(from - to).map(|_| rescale(price, from, to))
+    }
+}
+
+/// Fetch the base asset of the stellar oracle
+pub fn base(env: &Env) -> Asset {
+    let oracle = storage::get_stellar_oracle(env);
+    env.invoke_contract::<Asset>(&oracle, &Symbol::new(env, "base"), vec![env])
+}
+
+/// Fetch the resolution of the stellar oracle in seconds
+pub fn resolution(env: &Env) -> u32 {
+    let oracle = storage::get_stellar_oracle(env);
+    env.invoke_contract::<u32>(&oracle, &Symbol::new(env, "resolution"), vec![env])
+}
+
+/// Fetch the last price of the asset, or None if it is stale or moved more than the max deviation
+/// of the asset
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+pub fn load_price(env: &Env, asset: &Asset) -> Option<PriceData> {
+    match fetch_last(env, asset)? {
+        LastPrice { price, status: PriceStatus::Valid, .. } => Some(price),
+        _ => None,
+    }
+}
+
+/// Check if the price is older than the max age of the asset
+fn is_stale(env: &Env, asset: &Asset, price: &PriceData) -> bool {
+    match storage::get_max_age(env, asset) {
+        Some(max_age) => price.timestamp < env.ledger().timestamp().saturating_sub(max_age),
+        None => false,
+    }
+}
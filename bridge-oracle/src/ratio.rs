@@ -0,0 +1,83 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contracttype, vec, Address, Env, Error, IntoVal, Map, Symbol, TryFromVal, Val};
+use crate::{price, storage};
+
+/// The max decimals of a ratio
+const MAX_RATIO_DECIMALS: u32 = 18;
+
+/// The decimals of the b_rate of a Blend reserve
+const BLEND_RATE_DECIMALS: u32 = 12;
+
+/// Where the rate of a ratio comes from
+#[derive(Clone)]
+#[contracttype]
+pub enum Rate {
+    /// A fixed rate
+    Fixed(i128),
+    /// The rate returned by a function of a contract that takes no arguments, such as the
+    /// exchange rate of a liquid staking token
+    Contract(Address, Symbol),
+    /// The b_rate of the reserve of an asset in a Blend pool, the underlying value of a b_token
+    /// with 12 decimals
+    BlendBRate(Address, Address),
+}
+
+/// The ratio the price of an asset is scaled by, relative to the asset it is priced as
+#[derive(Clone)]
+#[contracttype]
+pub struct Ratio {
+    /// The rate of the asset to the asset it is priced as
+    pub rate: Rate,
+    /// The decimals of the rate
+    pub decimals: u32,
+}
+
+/// Fetch the current rate of a ratio, or None if it can't be fetched or is not positive
+/// # Arguments
+/// * `ratio` - The ratio to fetch the rate for
+pub fn current_rate(env: &Env, ratio: &Ratio) -> Option<i128> {
+    let rate = match &ratio.rate {
+        Rate::Fixed(rate) => *rate,
+        Rate::Contract(contract, function) => {
+            env.try_invoke_contract::<i128, Error>(contract, function, vec![env]).ok()?.ok()?
+        }
+        Rate::BlendBRate(pool, asset) => blend_b_rate(env, pool, asset)?,
+    };
+    if rate > 0 { Some(rate) } else { None }
+}
+
+/// Fetch the b_rate of the reserve of an asset in a Blend pool
+fn blend_b_rate(env: &Env, pool: &Address, asset: &Address) -> Option<i128> {
+    let reserve = price::try_invoke::<Map<Symbol, Val>>(env, pool, "get_reserve", vec![env, asset.into_val(env)])?;
+    let data = Map::<Symbol, Val>::try_from_val(env, &reserve.get(Symbol::new(env, "data"))?).ok()?;
+    i128::try_from_val(env, &data.get(Symbol::new(env, "b_rate"))?).ok()
+}
+
+/// Scale a price of an asset by the ratio of the asset, if it has one
+/// # Arguments
+/// * `asset` - The asset the price is for
+/// * `price` - The price of the asset it is priced as
+///
+/// Returns None if the asset has a ratio whose rate can't be fetched
+pub fn apply(env: &Env, asset: &Asset, price: PriceData) -> Option<PriceData> {
+    let ratio = match storage::get_ratio(env, asset) {
+        Some(ratio) => ratio,
+        None => return Some(price),
+    };
+    let rate = current_rate(env, &ratio)?;
+    let scalar = 10i128.pow(ratio.decimals);
+    Some(PriceData { price: price.price.fixed_mul_floor(env, &rate, &scalar), timestamp: price.timestamp })
+}
+
+/// Check a ratio has valid decimals and a positive current rate. A Blend b_rate must have the
+/// 12 decimals of the b_rate
+/// # Arguments
+/// * `ratio` - The ratio to check
+pub fn is_valid(env: &Env, ratio: &Ratio) -> bool {
+    let decimals = match ratio.rate {
+        Rate::BlendBRate(_, _) => ratio.decimals == BLEND_RATE_DECIMALS,
+        _ => ratio.decimals <= MAX_RATIO_DECIMALS,
+    };
+    decimals && current_rate(env, ratio).is_some()
+}
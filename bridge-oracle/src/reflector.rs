@@ -0,0 +1,41 @@
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{vec, Address, Env, IntoVal, Val, Vec};
+use crate::price;
+
+/// Fetch the resolution of a Reflector oracle in seconds, the time between its rounds
+/// # Arguments
+/// * `oracle` - The Reflector oracle
+pub fn resolution(env: &Env, oracle: &Address) -> Option<u64> {
+    price::try_invoke::<u32>(env, oracle, "resolution", vec![env])
+        .filter(|resolution| *resolution > 0)
+        .map(|resolution| resolution as u64)
+}
+
+/// Fetch the last price of an asset from a Reflector oracle. Reflector only returns the last
+/// price of an asset updated in its last round, so a price missing from that round is read from
+/// the round before
+/// # Arguments
+/// * `oracle` - The Reflector oracle
+/// * `asset` - The asset to fetch the price for
+pub fn lastprice(env: &Env, oracle: &Address, asset: &Asset) -> Option<PriceData> {
+    let args: Vec<Val> = vec![env, asset.into_val(env)];
+    if let Some(price) = price::try_invoke::<Option<PriceData>>(env, oracle, "lastprice", args).flatten() {
+        return Some(price);
+    }
+    let last_timestamp = price::try_invoke::<u64>(env, oracle, "last_timestamp", vec![env])?;
+    let previous = last_timestamp.checked_sub(resolution(env, oracle)?)?;
+    price(env, oracle, asset, previous)
+}
+
+/// Fetch the price of an asset at a timestamp from a Reflector oracle. Reflector only records
+/// prices at multiples of its resolution, so the timestamp is rounded down to the round it falls in
+/// # Arguments
+/// * `oracle` - The Reflector oracle
+/// * `asset` - The asset to fetch the price for
+/// * `timestamp` - The timestamp to fetch the price at
+pub fn price(env: &Env, oracle: &Address, asset: &Asset, timestamp: u64) -> Option<PriceData> {
+    let resolution = resolution(env, oracle)?;
+    let round = timestamp - timestamp % resolution;
+    let args: Vec<Val> = vec![env, asset.into_val(env), round.into_val(env)];
+    price::try_invoke::<Option<PriceData>>(env, oracle, "price", args).flatten()
+}
@@ -1,5 +1,16 @@
-use soroban_sdk::{Address, Env, contracttype};
-use sep_40_oracle::Asset;
+use soroban_sdk::{Address, Env, Vec, contracttype};
+use sep_40_oracle::{Asset, PriceData};
+use crate::adapter::Adapter;
+use crate::aggregate::PriceSources;
+use crate::bounds::PriceBounds;
+use crate::breaker::Breaker;
+use crate::cross::CrossLeg;
+use crate::fallback::FallbackOracle;
+use crate::history::PriceHistory;
+use crate::overrides::PriceOverride;
+use crate::price::{FixedPrice, LastPrice};
+use crate::ratio::Ratio;
+use crate::twap::{TwapConfig, TwapState};
 use soroban_sdk::unwrap::UnwrapOptimized;
 
 const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
@@ -15,6 +26,34 @@ pub enum BridgeOracleDataKey {
     StellarOracle,
     OtherOracle,
     BRIDGE(Asset),
+    ASSETS,
+    MaxAge(Asset),
+    SOURCES(Asset),
+    FALLBACKS(Asset),
+    CROSS(Asset),
+    FIXED(Asset),
+    GUARDIAN,
+    BREAKER(Asset),
+    LASTGOOD(Asset),
+    FROZEN(Asset),
+    TWAP(Asset),
+    TWAPSTATE(Asset),
+    ROUTE(Asset),
+    MAPPINGCOUNT,
+    MAPPING(u32),
+    MAPPINGINDEX(Asset),
+    OVERRIDE(Asset),
+    OVERRIDEBAND,
+    BOUNDS(Asset),
+    RATIO(Asset),
+    COMET(Address),
+    CACHE,
+    CACHED(Asset, u32),
+    ADAPTER(Address),
+    INVERSE(Asset),
+    HISTORYSIZE,
+    HISTORY(Asset),
+    DECIMALS(Address),
 }
 
 pub fn extend_instance(env: &Env) {
@@ -37,13 +76,16 @@ pub fn set_admin(e: &Env, new_admin: &Address) {
 }
 
 pub fn get_bridge_asset(env: &Env, asset: &Asset) -> Asset {
+    get_mapping(env, asset).unwrap_or(asset.clone())
+}
+
+pub fn get_mapping(env: &Env, asset: &Asset) -> Option<Asset> {
     let key = BridgeOracleDataKey::BRIDGE(asset.clone());
-    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, Asset>(&key) {
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Asset>(&key);
+    if result.is_some() {
         env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
-        result
-    } else {
-        asset.clone()
     }
+    result
 }
 
 pub fn set_bridge_asset(env: &Env, asset: &Asset, to: &Asset) {
@@ -52,6 +94,370 @@ pub fn set_bridge_asset(env: &Env, asset: &Asset, to: &Asset) {
     env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
 }
 
+pub fn remove_bridge_asset(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::BRIDGE(asset.clone()));
+}
+
+pub fn get_mapping_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::MAPPINGCOUNT)
+        .unwrap_or(0)
+}
+
+pub fn set_mapping_count(env: &Env, count: u32) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::MAPPINGCOUNT, &count);
+}
+
+// each mapped asset is kept in its own entry, so listing them doesn't load every mapping
+pub fn get_mapped_asset(env: &Env, index: u32) -> Option<Asset> {
+    let key = BridgeOracleDataKey::MAPPING(index);
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Asset>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_mapped_asset(env: &Env, index: u32, asset: &Asset) {
+    let key = BridgeOracleDataKey::MAPPING(index);
+    env.storage().persistent().set::<BridgeOracleDataKey, Asset>(&key, asset);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_mapped_asset(env: &Env, index: u32) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::MAPPING(index));
+}
+
+pub fn get_mapping_index(env: &Env, asset: &Asset) -> Option<u32> {
+    let key = BridgeOracleDataKey::MAPPINGINDEX(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, u32>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_mapping_index(env: &Env, asset: &Asset, index: u32) {
+    let key = BridgeOracleDataKey::MAPPINGINDEX(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, u32>(&key, &index);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_mapping_index(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::MAPPINGINDEX(asset.clone()));
+}
+
+pub fn get_assets(env: &Env) -> Vec<Asset> {
+    // kept out of instance storage, which is loaded by every call
+    let key = BridgeOracleDataKey::ASSETS;
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, Vec<Asset>>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        Vec::new(env)
+    }
+}
+
+pub fn set_assets(env: &Env, assets: &Vec<Asset>) {
+    let key = BridgeOracleDataKey::ASSETS;
+    env.storage().persistent().set::<BridgeOracleDataKey, Vec<Asset>>(&key, assets);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_max_age(env: &Env, asset: &Asset) -> Option<u64> {
+    let key = BridgeOracleDataKey::MaxAge(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, u64>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_max_age(env: &Env, asset: &Asset, max_age: &u64) {
+    let key = BridgeOracleDataKey::MaxAge(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, u64>(&key, max_age);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_max_age(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::MaxAge(asset.clone()));
+}
+
+pub fn get_price_sources(env: &Env, asset: &Asset) -> Option<PriceSources> {
+    let key = BridgeOracleDataKey::SOURCES(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, PriceSources>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_price_sources(env: &Env, asset: &Asset, sources: &PriceSources) {
+    let key = BridgeOracleDataKey::SOURCES(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, PriceSources>(&key, sources);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_price_sources(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::SOURCES(asset.clone()));
+}
+
+pub fn get_fallbacks(env: &Env, asset: &Asset) -> Vec<FallbackOracle> {
+    let key = BridgeOracleDataKey::FALLBACKS(asset.clone());
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, Vec<FallbackOracle>>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        Vec::new(env)
+    }
+}
+
+pub fn set_fallbacks(env: &Env, asset: &Asset, fallbacks: &Vec<FallbackOracle>) {
+    let key = BridgeOracleDataKey::FALLBACKS(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Vec<FallbackOracle>>(&key, fallbacks);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_fallbacks(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::FALLBACKS(asset.clone()));
+}
+
+pub fn get_cross_rate(env: &Env, asset: &Asset) -> Option<Vec<CrossLeg>> {
+    let key = BridgeOracleDataKey::CROSS(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Vec<CrossLeg>>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_cross_rate(env: &Env, asset: &Asset, legs: &Vec<CrossLeg>) {
+    let key = BridgeOracleDataKey::CROSS(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Vec<CrossLeg>>(&key, legs);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_cross_rate(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::CROSS(asset.clone()));
+}
+
+pub fn get_fixed_price(env: &Env, asset: &Asset) -> Option<FixedPrice> {
+    let key = BridgeOracleDataKey::FIXED(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, FixedPrice>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_fixed_price(env: &Env, asset: &Asset, fixed: &FixedPrice) {
+    let key = BridgeOracleDataKey::FIXED(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, FixedPrice>(&key, fixed);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_fixed_price(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::FIXED(asset.clone()));
+}
+
+pub fn get_guardian(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::GUARDIAN)
+}
+
+pub fn set_guardian(env: &Env, guardian: &Address) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::GUARDIAN, guardian);
+}
+
+pub fn get_ratio(env: &Env, asset: &Asset) -> Option<Ratio> {
+    let key = BridgeOracleDataKey::RATIO(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Ratio>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_ratio(env: &Env, asset: &Asset, ratio: &Ratio) {
+    let key = BridgeOracleDataKey::RATIO(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Ratio>(&key, ratio);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_ratio(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::RATIO(asset.clone()));
+}
+
+pub fn get_bounds(env: &Env, asset: &Asset) -> Option<PriceBounds> {
+    let key = BridgeOracleDataKey::BOUNDS(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, PriceBounds>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_bounds(env: &Env, asset: &Asset, bounds: &PriceBounds) {
+    let key = BridgeOracleDataKey::BOUNDS(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, PriceBounds>(&key, bounds);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_bounds(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::BOUNDS(asset.clone()));
+}
+
+pub fn get_override(env: &Env, asset: &Asset) -> Option<PriceOverride> {
+    let key = BridgeOracleDataKey::OVERRIDE(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, PriceOverride>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_override(env: &Env, asset: &Asset, price_override: &PriceOverride) {
+    let key = BridgeOracleDataKey::OVERRIDE(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, PriceOverride>(&key, price_override);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_override(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::OVERRIDE(asset.clone()));
+}
+
+pub fn get_override_band(env: &Env) -> Option<i128> {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::OVERRIDEBAND)
+}
+
+pub fn set_override_band(env: &Env, band: &i128) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::OVERRIDEBAND, band);
+}
+
+pub fn remove_override_band(env: &Env) {
+    env.storage()
+        .instance()
+        .remove(&BridgeOracleDataKey::OVERRIDEBAND);
+}
+
+pub fn get_breaker(env: &Env, asset: &Asset) -> Option<Breaker> {
+    let key = BridgeOracleDataKey::BREAKER(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Breaker>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_breaker(env: &Env, asset: &Asset, breaker: &Breaker) {
+    let key = BridgeOracleDataKey::BREAKER(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Breaker>(&key, breaker);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_breaker(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::BREAKER(asset.clone()));
+}
+
+pub fn get_last_good_price(env: &Env, asset: &Asset) -> Option<PriceData> {
+    let key = BridgeOracleDataKey::LASTGOOD(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, PriceData>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_last_good_price(env: &Env, asset: &Asset, price: &PriceData) {
+    let key = BridgeOracleDataKey::LASTGOOD(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, PriceData>(&key, price);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_frozen(env: &Env, asset: &Asset) -> bool {
+    let key = BridgeOracleDataKey::FROZEN(asset.clone());
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, bool>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        false
+    }
+}
+
+pub fn set_frozen(env: &Env, asset: &Asset, frozen: bool) {
+    let key = BridgeOracleDataKey::FROZEN(asset.clone());
+    if frozen {
+        env.storage().persistent().set::<BridgeOracleDataKey, bool>(&key, &true);
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn get_twap(env: &Env, asset: &Asset) -> Option<TwapConfig> {
+    let key = BridgeOracleDataKey::TWAP(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, TwapConfig>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_twap(env: &Env, asset: &Asset, config: &TwapConfig) {
+    let key = BridgeOracleDataKey::TWAP(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, TwapConfig>(&key, config);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_twap(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::TWAP(asset.clone()));
+    env.storage().persistent().remove(&BridgeOracleDataKey::TWAPSTATE(asset.clone()));
+}
+
+pub fn get_twap_state(env: &Env, asset: &Asset) -> Option<TwapState> {
+    let key = BridgeOracleDataKey::TWAPSTATE(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, TwapState>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_twap_state(env: &Env, asset: &Asset, state: &TwapState) {
+    let key = BridgeOracleDataKey::TWAPSTATE(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, TwapState>(&key, state);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_route(env: &Env, asset: &Asset) -> Option<Address> {
+    let key = BridgeOracleDataKey::ROUTE(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, Address>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_route(env: &Env, asset: &Asset, oracle: &Address) {
+    let key = BridgeOracleDataKey::ROUTE(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Address>(&key, oracle);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_route(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::ROUTE(asset.clone()));
+}
+
 pub fn get_stellar_oracle(env: &Env) -> Address {
     env.storage()
         .instance()
@@ -77,4 +483,130 @@ pub fn set_other_oracle(env: &Env, address: &Address) {
     env.storage()
         .instance()
         .set(&BridgeOracleDataKey::OtherOracle, address);
+}
+
+pub fn get_comet_pool(env: &Env, pool: &Address) -> bool {
+    let key = BridgeOracleDataKey::COMET(pool.clone());
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, bool>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        false
+    }
+}
+
+pub fn set_comet_pool(env: &Env, pool: &Address) {
+    let key = BridgeOracleDataKey::COMET(pool.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, bool>(&key, &true);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_comet_pool(env: &Env, pool: &Address) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::COMET(pool.clone()));
+}
+
+pub fn get_cache(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::CACHE)
+        .unwrap_or(false)
+}
+
+pub fn set_cache(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::CACHE, &enabled);
+}
+
+pub fn get_cached_price(env: &Env, asset: &Asset, sequence: u32) -> Option<LastPrice> {
+    env.storage()
+        .temporary()
+        .get::<BridgeOracleDataKey, LastPrice>(&BridgeOracleDataKey::CACHED(asset.clone(), sequence))
+}
+
+pub fn set_cached_price(env: &Env, asset: &Asset, sequence: u32, last: &LastPrice) {
+    // the entry is only read in its own ledger, so it is left to expire at the min temporary TTL
+    env.storage()
+        .temporary()
+        .set::<BridgeOracleDataKey, LastPrice>(&BridgeOracleDataKey::CACHED(asset.clone(), sequence), last);
+}
+
+pub fn get_adapter(env: &Env, oracle: &Address) -> Adapter {
+    let key = BridgeOracleDataKey::ADAPTER(oracle.clone());
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, Adapter>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        Adapter::Sep40
+    }
+}
+
+pub fn set_adapter(env: &Env, oracle: &Address, adapter: &Adapter) {
+    let key = BridgeOracleDataKey::ADAPTER(oracle.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, Adapter>(&key, adapter);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_adapter(env: &Env, oracle: &Address) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::ADAPTER(oracle.clone()));
+}
+
+pub fn get_inverse(env: &Env, asset: &Asset) -> bool {
+    let key = BridgeOracleDataKey::INVERSE(asset.clone());
+    if let Some(result) = env.storage().persistent().get::<BridgeOracleDataKey, bool>(&key) {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        false
+    }
+}
+
+pub fn set_inverse(env: &Env, asset: &Asset) {
+    let key = BridgeOracleDataKey::INVERSE(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, bool>(&key, &true);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn remove_inverse(env: &Env, asset: &Asset) {
+    env.storage().persistent().remove(&BridgeOracleDataKey::INVERSE(asset.clone()));
+}
+
+pub fn get_history_size(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::HISTORYSIZE)
+        .unwrap_or(0)
+}
+
+pub fn set_history_size(env: &Env, size: u32) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::HISTORYSIZE, &size);
+}
+
+pub fn get_history(env: &Env, asset: &Asset) -> Option<PriceHistory> {
+    let key = BridgeOracleDataKey::HISTORY(asset.clone());
+    let result = env.storage().persistent().get::<BridgeOracleDataKey, PriceHistory>(&key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+    }
+    result
+}
+
+pub fn set_history(env: &Env, asset: &Asset, history: &PriceHistory) {
+    let key = BridgeOracleDataKey::HISTORY(asset.clone());
+    env.storage().persistent().set::<BridgeOracleDataKey, PriceHistory>(&key, history);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_decimals(env: &Env, oracle: &Address) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get(&BridgeOracleDataKey::DECIMALS(oracle.clone()))
+}
+
+pub fn set_decimals(env: &Env, oracle: &Address, decimals: u32) {
+    env.storage()
+        .instance()
+        .set(&BridgeOracleDataKey::DECIMALS(oracle.clone()), &decimals);
 }
\ No newline at end of file
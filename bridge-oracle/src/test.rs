@@ -1,11 +1,16 @@
 #[cfg(test)]
 
-use sep_40_oracle::Asset;
+use sep_40_oracle::{Asset, PriceData};
 use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
 use sep_40_oracle::testutils::Asset as TestAsset;
-use soroban_sdk::{testutils::Address as _, Address, Env, IntoVal, Symbol, vec as svec, symbol_short};
-use soroban_sdk::testutils::{AuthorizedFunction, AuthorizedInvocation};
-use crate::{BridgeOracleContract, BridgeOracleClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Event, IntoVal, Symbol, vec as svec, symbol_short};
+use soroban_sdk::{contract, contractimpl};
+use soroban_sdk::testutils::{AuthorizedFunction, AuthorizedInvocation, Events, Ledger};
+use crate::{BridgeOracleContract, BridgeOracleClient, BridgeOracleError};
+use crate::price::rescale;
+use crate::storage::BridgeOracleDataKey;
+use crate::events::{FallbackUsed, OverrideUsed, PriceOutOfBounds, EVENT_VERSION};
+use crate::{Adapter, CrossLeg, FallbackOracle, Mapping, PriceSource, PriceStatus, Rate};
 
 pub(crate) fn create_mock_oracle(e: &Env) -> (Address, MockPriceOracleClient) {
     let contract_address = e.register(MockPriceOracleWASM, ());
@@ -154,4 +159,1844 @@ fn test_update_oracle() {
             }
         )]
     );
-}
\ No newline at end of file
+}
+fn create_bridge_oracle_with_usd<'a>(env: &'a Env, admin: &Address, token: &Address) -> (MockPriceOracleClient<'a>, BridgeOracleClient<'a>) {
+    let (oracle_address, mock_oracle_client) = create_mock_oracle(env);
+    mock_oracle_client.set_data(
+        admin,
+        &TestAsset::Other(Symbol::new(env, "USD")),
+        &svec![env, TestAsset::Other(Symbol::new(env, "EURO"))],
+        &14,
+        &300,
+    );
+    let (stellar_oracle, mock_stellar_oracle_client) = create_mock_oracle(env);
+    mock_stellar_oracle_client.set_data(
+        admin,
+        &TestAsset::Other(Symbol::new(env, "USD")),
+        &svec![env],
+        &14,
+        &300,
+    );
+
+    let bridge_oracle_address = env.register(BridgeOracleContract, (admin.clone(), stellar_oracle, oracle_address));
+    let bridge_oracle_client = BridgeOracleClient::new(env, &bridge_oracle_address);
+    bridge_oracle_client.add_asset(&Asset::Stellar(token.clone()), &Asset::Other(Symbol::new(env, "EURO")));
+    (mock_oracle_client, bridge_oracle_client)
+}
+
+#[test]
+fn test_max_age() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_usd(&env, &admin, &token);
+    let asset = Asset::Stellar(token.clone());
+    mock_oracle_client.set_price(&svec![&env, 1_10_000_000_000_000], &9_000);
+
+    // without a max age any price is returned
+    assert_eq!(bridge_oracle_client.max_age(&asset), None);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().timestamp, 9_000);
+
+    bridge_oracle_client.set_max_age(&asset, &900);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_max_age"),
+                    (asset.clone(), 900_u64).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.max_age(&asset), Some(900));
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    assert_eq!(
+        bridge_oracle_client.try_lastprice_strict(&asset).err(),
+        Some(Ok(BridgeOracleError::StalePriceError.into()))
+    );
+
+    mock_oracle_client.set_price(&svec![&env, 1_10_000_000_000_000], &9_300);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10_000_000_000_000);
+    assert_eq!(bridge_oracle_client.lastprice_strict(&asset).timestamp, 9_300);
+
+    bridge_oracle_client.set_max_age(&asset, &0);
+    assert_eq!(bridge_oracle_client.max_age(&asset), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1401)")]
+fn test_lastprice_strict_not_found() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, bridge_oracle_client) = create_bridge_oracle_with_usd(&env, &admin, &token);
+
+    bridge_oracle_client.lastprice_strict(&Asset::Stellar(token));
+}
+
+#[test]
+fn test_sep_40_interface() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token1 = Address::generate(&env);
+    let token2 = Address::generate(&env);
+    let xlm_address = Address::generate(&env);
+
+    let (oracle_address, mock_oracle_client) = create_mock_oracle(&env);
+    mock_oracle_client.set_data(
+        &admin,
+        &TestAsset::Other(Symbol::new(&env, "USD")),
+        &svec![&env, TestAsset::Other(Symbol::new(&env, "EURO"))],
+        &14,
+        &300,
+    );
+    mock_oracle_client.set_price(&svec![&env, 1_10_000_000_000_000], &9_600);
+    mock_oracle_client.set_price(&svec![&env, 1_12_000_000_000_000], &9_900);
+
+    let (stellar_oracle_address, mock_stellar_oracle_client) = create_mock_oracle(&env);
+    mock_stellar_oracle_client.set_data(
+        &admin,
+        &TestAsset::Other(Symbol::new(&env, "USD")),
+        &svec![&env, TestAsset::Stellar(xlm_address.clone())],
+        &14,
+        &300,
+    );
+    mock_stellar_oracle_client.set_price(&svec![&env, 0_10_000_000_000_000], &9_900);
+
+    let bridge_oracle_address = env.register(BridgeOracleContract, (admin.clone(), stellar_oracle_address, oracle_address));
+    let bridge_oracle_client = BridgeOracleClient::new(&env, &bridge_oracle_address);
+
+    let stellar_asset = Asset::Stellar(token1.clone());
+    let stellar_asset2 = Asset::Stellar(token2.clone());
+    let usd_asset = Asset::Other(Symbol::new(&env, "USD"));
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+    let xlm_asset = Asset::Stellar(xlm_address.clone());
+    bridge_oracle_client.add_asset(&stellar_asset, &usd_asset);
+    bridge_oracle_client.add_asset(&stellar_asset2, &euro_asset);
+    bridge_oracle_client.add_asset(&stellar_asset2, &euro_asset);
+
+    assert!(matches!(bridge_oracle_client.base(), Asset::Other(base) if base == Symbol::new(&env, "USD")));
+    assert_eq!(bridge_oracle_client.resolution(), 300);
+    assert_eq!(bridge_oracle_client.decimals(), 14);
+    assert!(bridge_oracle_client.assets() == svec![&env, stellar_asset.clone(), stellar_asset2.clone()]);
+    // the asset list is kept out of instance storage, which every call loads
+    env.as_contract(&bridge_oracle_client.address, || {
+        assert!(!env.storage().instance().has(&BridgeOracleDataKey::ASSETS));
+        assert!(env.storage().persistent().has(&BridgeOracleDataKey::ASSETS));
+    });
+
+    // other assets route to the other oracle
+    let euro_price = bridge_oracle_client.price(&stellar_asset2, &9_600).unwrap();
+    assert_eq!(euro_price.price, 1_10_000_000_000_000);
+    assert_eq!(euro_price.timestamp, 9_600);
+    let euro_prices = bridge_oracle_client.prices(&stellar_asset2, &2).unwrap();
+    assert_eq!(euro_prices.len(), 2);
+    assert_eq!(euro_prices.get(0).unwrap().price, 1_12_000_000_000_000);
+    assert_eq!(euro_prices.get(1).unwrap().price, 1_10_000_000_000_000);
+
+    // stellar assets route to the stellar oracle
+    assert_eq!(bridge_oracle_client.price(&xlm_asset, &9_900).unwrap().price, 0_10_000_000_000_000);
+    assert_eq!(bridge_oracle_client.prices(&xlm_asset, &1).unwrap().get(0).unwrap().price, 0_10_000_000_000_000);
+
+    // USD has a fixed price at any timestamp
+    let usd_price = bridge_oracle_client.price(&stellar_asset, &9_000).unwrap();
+    assert_eq!(usd_price.price, 1_00_000_000_000_000);
+    assert_eq!(usd_price.timestamp, 9_000);
+    // at the same timestamps as aggregated prices, floored to the resolution
+    assert_eq!(bridge_oracle_client.price(&stellar_asset, &9_950).unwrap().timestamp, 9_900);
+    let usd_prices = bridge_oracle_client.prices(&stellar_asset, &3).unwrap();
+    assert_eq!(usd_prices.len(), 3);
+    assert_eq!(usd_prices.get(0).unwrap().timestamp, 9_900);
+    assert_eq!(usd_prices.get(2).unwrap().timestamp, 9_300);
+    assert_eq!(usd_prices.get(2).unwrap().price, 1_00_000_000_000_000);
+}
+
+fn create_bridge_oracle_with_decimals<'a>(
+    env: &'a Env,
+    admin: &Address,
+    stellar_decimals: u32,
+    other_decimals: u32,
+) -> (MockPriceOracleClient<'a>, MockPriceOracleClient<'a>, BridgeOracleClient<'a>) {
+    let (oracle_address, mock_oracle_client) = create_mock_oracle(env);
+    mock_oracle_client.set_data(
+        admin,
+        &TestAsset::Other(Symbol::new(env, "USD")),
+        &svec![env, TestAsset::Other(Symbol::new(env, "EURO"))],
+        &other_decimals,
+        &300,
+    );
+    let (stellar_oracle_address, mock_stellar_oracle_client) = create_mock_oracle(env);
+    mock_stellar_oracle_client.set_data(
+        admin,
+        &TestAsset::Other(Symbol::new(env, "USD")),
+        &svec![env, TestAsset::Stellar(Address::generate(env))],
+        &stellar_decimals,
+        &300,
+    );
+
+    let bridge_oracle_address = env.register(BridgeOracleContract, (admin.clone(), stellar_oracle_address, oracle_address));
+    (mock_stellar_oracle_client, mock_oracle_client, BridgeOracleClient::new(env, &bridge_oracle_address))
+}
+
+#[test]
+fn test_decimals_scale_down() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    assert_eq!(bridge_oracle_client.decimals(), 7);
+
+    // 1.08765435 rounds up to 1.0876544
+    mock_oracle_client.set_price(&svec![&env, 1_08765435000000], &9_600);
+    // 1.08765434999999 rounds down to 1.0876543
+    mock_oracle_client.set_price(&svec![&env, 1_08765434999999], &9_900);
+
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_0876543);
+    assert_eq!(bridge_oracle_client.price(&asset, &9_600).unwrap().price, 1_0876544);
+    let prices = bridge_oracle_client.prices(&asset, &2).unwrap();
+    assert_eq!(prices.get(0).unwrap().price, 1_0876543);
+    assert_eq!(prices.get(1).unwrap().price, 1_0876544);
+
+    // USD is quoted in the decimals of the bridge oracle
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "USD")));
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_0000000);
+}
+
+#[test]
+fn test_decimals_scale_up() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (mock_stellar_oracle_client, mock_oracle_client, bridge_oracle_client) =
+        create_bridge_oracle_with_decimals(&env, &admin, 14, 6);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    assert_eq!(bridge_oracle_client.decimals(), 14);
+
+    mock_oracle_client.set_price(&svec![&env, 1_087654], &9_900);
+    mock_stellar_oracle_client.set_price(&svec![&env, 0_12345678901234], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_08765400000000);
+
+    // stellar assets are not rescaled
+    let xlm = mock_stellar_oracle_client.assets().get(0).unwrap();
+    let xlm_asset = match xlm {
+        TestAsset::Stellar(address) => Asset::Stellar(address),
+        TestAsset::Other(symbol) => Asset::Other(symbol),
+    };
+    assert_eq!(bridge_oracle_client.lastprice(&xlm_asset).unwrap().price, 0_12345678901234);
+}
+
+#[test]
+fn test_rescale() {
+    assert_eq!(rescale(1_2345, 4, 4), 1_2345);
+    assert_eq!(rescale(1_2345, 4, 7), 1_2345000);
+    assert_eq!(rescale(1_2345, 4, 3), 1_235);
+    assert_eq!(rescale(1_2344, 4, 3), 1_234);
+    assert_eq!(rescale(-1_2345, 4, 3), -1_235);
+    assert_eq!(rescale(4, 1, 0), 0);
+    assert_eq!(rescale(5, 1, 0), 1);
+}
+
+fn create_euro_source(env: &Env, admin: &Address, decimals: u32, price: i128, timestamp: u64) -> Address {
+    let (address, client) = create_mock_oracle(env);
+    client.set_data(
+        admin,
+        &TestAsset::Other(Symbol::new(env, "USD")),
+        &svec![env, TestAsset::Other(Symbol::new(env, "EURO"))],
+        &decimals,
+        &300,
+    );
+    client.set_price(&svec![env, price], &timestamp);
+    address
+}
+
+#[test]
+fn test_price_sources_median() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+    bridge_oracle_client.add_asset(&asset, &euro_asset);
+
+    let source1 = create_euro_source(&env, &admin, 14, 1_08000000000000, 9_900);
+    let source2 = create_euro_source(&env, &admin, 7, 1_0900000, 9_600);
+    let source3 = create_euro_source(&env, &admin, 18, 1_100000000000000000, 9_900);
+    let sources = svec![&env, source1.clone(), source2.clone(), source3.clone()];
+    bridge_oracle_client.set_price_sources(&euro_asset, &sources, &2, &0_0500000, &600);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_price_sources"),
+                    (euro_asset.clone(), sources.clone(), 2_u32, 0_0500000_i128, 600_u64).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let config = bridge_oracle_client.price_sources(&euro_asset).unwrap();
+    assert_eq!(config.sources, sources);
+    assert_eq!(config.quorum, 2);
+
+    // the median of the rescaled prices, with the oldest timestamp that took part
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_09000000000000);
+    assert_eq!(price.timestamp, 9_600);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), sources);
+
+    // stale sources are ignored and the median falls between the remaining prices
+    env.ledger().set_timestamp(10_300);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_09000000000000);
+    assert_eq!(price.timestamp, 9_900);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, source1.clone(), source3.clone()]);
+
+    // sources beyond the tolerance are ignored, leaving no quorum
+    bridge_oracle_client.set_price_sources(&euro_asset, &sources, &2, &0_0050000, &600);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset).len(), 0);
+
+    // sources beyond the tolerance are ignored
+    env.ledger().set_timestamp(10_000);
+    bridge_oracle_client.set_price_sources(&euro_asset, &sources, &1, &0_0050000, &600);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_09000000000000);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, source2.clone()]);
+
+    // removing the sources falls back to the other oracle
+    bridge_oracle_client.set_price_sources(&euro_asset, &svec![&env], &0, &0, &0);
+    assert!(bridge_oracle_client.price_sources(&euro_asset).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1403)")]
+fn test_price_sources_quorum_above_sources() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let source = create_euro_source(&env, &admin, 14, 1_08000000000000, 0);
+
+    bridge_oracle_client.set_price_sources(
+        &Asset::Other(Symbol::new(&env, "EURO")),
+        &svec![&env, source],
+        &2,
+        &0_0500000,
+        &0,
+    );
+}
+
+#[test]
+fn test_fallbacks() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+
+    let stale_fallback = create_euro_source(&env, &admin, 14, 1_09000000000000, 9_000);
+    let fresh_fallback = create_euro_source(&env, &admin, 7, 1_1000000, 9_900);
+    let fallbacks = svec![
+        &env,
+        FallbackOracle { oracle: stale_fallback.clone(), max_age: 600 },
+        FallbackOracle { oracle: fresh_fallback.clone(), max_age: 1_000 },
+    ];
+    bridge_oracle_client.set_fallbacks(&asset, &fallbacks);
+    assert_eq!(bridge_oracle_client.fallbacks(&asset).len(), 2);
+
+    // the primary oracle is fresh
+    mock_oracle_client.set_price(&svec![&env, 1_08000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_08000000000000);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, mock_oracle_client.address.clone()]);
+
+    // the primary oracle is stale, so the first fresh fallback is used
+    bridge_oracle_client.set_max_age(&asset, &600);
+    env.ledger().set_timestamp(11_000);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    env.ledger().set_timestamp(10_800);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_10000000000000);
+    assert_eq!(price.timestamp, 9_900);
+    let event = FallbackUsed { version: EVENT_VERSION, asset: asset.clone(), oracle: fresh_fallback.clone() };
+    assert_eq!(
+        env.events().all(),
+        svec![&env, (bridge_oracle_client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, fresh_fallback.clone()]);
+
+    bridge_oracle_client.set_fallbacks(&asset, &svec![&env]);
+    assert_eq!(bridge_oracle_client.fallbacks(&asset).len(), 0);
+}
+
+#[test]
+fn test_fallbacks_primary_fails() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let asset = Asset::Stellar(token.clone());
+    let (stellar_oracle, mock_stellar_oracle_client) = create_mock_oracle(&env);
+    mock_stellar_oracle_client.set_data(&admin, &TestAsset::Other(Symbol::new(&env, "USD")), &svec![&env], &14, &300);
+    // the other oracle lists the asset but has no price for it
+    let (other_oracle, mock_oracle_client) = create_mock_oracle(&env);
+    mock_oracle_client.set_data(
+        &admin,
+        &TestAsset::Other(Symbol::new(&env, "USD")),
+        &svec![&env, TestAsset::Other(Symbol::new(&env, "EURO"))],
+        &14,
+        &300,
+    );
+    let bridge_oracle_address = env.register(BridgeOracleContract, (admin.clone(), stellar_oracle, other_oracle));
+    let bridge_oracle_client = BridgeOracleClient::new(&env, &bridge_oracle_address);
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+
+    let fallback = create_euro_source(&env, &admin, 14, 1_09000000000000, 9_900);
+    bridge_oracle_client.set_fallbacks(&asset, &svec![&env, FallbackOracle { oracle: fallback.clone(), max_age: 0 }]);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_09000000000000);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, fallback]);
+}
+
+/// An oracle that panics on every call once it is set to fail
+#[contract]
+pub struct MockFailingOracle;
+
+#[contractimpl]
+impl MockFailingOracle {
+    pub fn set_data(e: Env, assets: soroban_sdk::Vec<Asset>, decimals: u32) {
+        e.storage().instance().set(&symbol_short!("ASSETS"), &assets);
+        e.storage().instance().set(&symbol_short!("DECIMALS"), &decimals);
+    }
+
+    pub fn set_failing(e: Env, failing: bool) {
+        e.storage().instance().set(&symbol_short!("FAILING"), &failing);
+    }
+
+    pub fn assets(e: Env) -> soroban_sdk::Vec<Asset> {
+        Self::check(&e);
+        e.storage().instance().get(&symbol_short!("ASSETS")).unwrap()
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        Self::check(&e);
+        e.storage().instance().get(&symbol_short!("DECIMALS")).unwrap()
+    }
+
+    pub fn lastprice(e: Env, _asset: Asset) -> Option<PriceData> {
+        Self::check(&e);
+        None
+    }
+
+    pub fn price(e: Env, _asset: Asset, _timestamp: u64) -> Option<PriceData> {
+        Self::check(&e);
+        None
+    }
+
+    pub fn prices(e: Env, _asset: Asset, _records: u32) -> Option<soroban_sdk::Vec<PriceData>> {
+        Self::check(&e);
+        None
+    }
+}
+
+impl MockFailingOracle {
+    fn check(e: &Env) {
+        if e.storage().instance().get(&symbol_short!("FAILING")).unwrap_or(false) {
+            panic!("oracle is down");
+        }
+    }
+}
+
+#[test]
+fn test_fallbacks_primary_panics() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let asset = Asset::Stellar(Address::generate(&env));
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+    let stellar_oracle = env.register(MockFailingOracle, ());
+    let stellar_oracle_client = MockFailingOracleClient::new(&env, &stellar_oracle);
+    stellar_oracle_client.set_data(&svec![&env], &14);
+    let other_oracle = env.register(MockFailingOracle, ());
+    let other_oracle_client = MockFailingOracleClient::new(&env, &other_oracle);
+    other_oracle_client.set_data(&svec![&env, euro_asset.clone()], &14);
+    let bridge_oracle_address = env.register(BridgeOracleContract, (admin.clone(), stellar_oracle, other_oracle));
+    let bridge_oracle_client = BridgeOracleClient::new(&env, &bridge_oracle_address);
+    bridge_oracle_client.add_asset(&asset, &euro_asset);
+    let fallback = create_euro_source(&env, &admin, 7, 1_0900000, 9_900);
+    bridge_oracle_client.set_fallbacks(&asset, &svec![&env, FallbackOracle { oracle: fallback.clone(), max_age: 0 }]);
+
+    // both oracles panic on every call, including for their decimals
+    stellar_oracle_client.set_failing(&true);
+    other_oracle_client.set_failing(&true);
+    assert_eq!(bridge_oracle_client.decimals(), 14);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_09000000000000);
+    assert_eq!(price.timestamp, 9_900);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, fallback]);
+}
+
+#[test]
+fn test_cross_rate() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let (mock_stellar_oracle_client, mock_oracle_client, bridge_oracle_client) =
+        create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+    mock_stellar_oracle_client.set_price(&svec![&env, 0_1000000], &9_600);
+    mock_stellar_oracle_client.set_price(&svec![&env, 0_2000000], &9_900);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_600);
+    mock_oracle_client.set_price(&svec![&env, 1_20000000000000], &9_900);
+    let xlm_asset = match mock_stellar_oracle_client.assets().get(0).unwrap() {
+        TestAsset::Stellar(address) => Asset::Stellar(address),
+        TestAsset::Other(symbol) => Asset::Other(symbol),
+    };
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+
+    // EUR/XLM = EUR/USD / XLM/USD
+    let eur_xlm = Asset::Other(Symbol::new(&env, "EURXLM"));
+    let legs = svec![
+        &env,
+        CrossLeg { asset: euro_asset.clone(), inverse: false },
+        CrossLeg { asset: xlm_asset.clone(), inverse: true },
+    ];
+    bridge_oracle_client.set_cross_rate(&eur_xlm, &legs);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_cross_rate"),
+                    (eur_xlm.clone(), legs.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.cross_rate(&eur_xlm).unwrap().len(), 2);
+    assert!(bridge_oracle_client.assets() == svec![&env, eur_xlm.clone()]);
+
+    let price = bridge_oracle_client.lastprice(&eur_xlm).unwrap();
+    assert_eq!(price.price, 6_0000000);
+    assert_eq!(price.timestamp, 9_900);
+    assert_eq!(bridge_oracle_client.price(&eur_xlm, &9_600).unwrap().price, 11_0000000);
+    let prices = bridge_oracle_client.prices(&eur_xlm, &2).unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices.get(0).unwrap().price, 6_0000000);
+    assert_eq!(prices.get(1).unwrap().price, 11_0000000);
+    assert_eq!(
+        bridge_oracle_client.lastprice_sources(&eur_xlm),
+        svec![&env, mock_oracle_client.address.clone(), mock_stellar_oracle_client.address.clone()]
+    );
+
+    // a stellar asset priced from a stellar oracle feed and an other oracle feed
+    let token = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.set_cross_rate(&token, &svec![
+        &env,
+        CrossLeg { asset: xlm_asset.clone(), inverse: false },
+        CrossLeg { asset: euro_asset.clone(), inverse: false },
+    ]);
+    assert_eq!(bridge_oracle_client.lastprice(&token).unwrap().price, 0_2400000);
+
+    // a missing leg has no price
+    bridge_oracle_client.set_cross_rate(&token, &svec![
+        &env,
+        CrossLeg { asset: xlm_asset.clone(), inverse: false },
+        CrossLeg { asset: Asset::Other(Symbol::new(&env, "GBP")), inverse: false },
+    ]);
+    assert!(bridge_oracle_client.lastprice(&token).is_none());
+
+    bridge_oracle_client.set_cross_rate(&eur_xlm, &svec![&env]);
+    assert!(bridge_oracle_client.cross_rate(&eur_xlm).is_none());
+}
+
+#[test]
+fn test_cross_rate_mapped_legs() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_600);
+    mock_oracle_client.set_price(&svec![&env, 1_20000000000000], &9_900);
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+
+    // legs priced as the inverse of EURO, and as EURO scaled by a ratio
+    let inverse_leg = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.add_inverse_asset(&inverse_leg, &euro_asset);
+    let ratio_leg = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.add_asset(&ratio_leg, &euro_asset);
+    bridge_oracle_client.set_ratio(&ratio_leg, &Rate::Fixed(2_0000000), &7);
+
+    // each leg is priced like the asset itself
+    let cross = Asset::Other(Symbol::new(&env, "CROSS"));
+    bridge_oracle_client.set_cross_rate(&cross, &svec![
+        &env,
+        CrossLeg { asset: inverse_leg.clone(), inverse: false },
+        CrossLeg { asset: ratio_leg.clone(), inverse: false },
+    ]);
+    assert_eq!(bridge_oracle_client.lastprice(&inverse_leg).unwrap().price, 0_8333333);
+    assert_eq!(bridge_oracle_client.lastprice(&ratio_leg).unwrap().price, 2_4000000);
+    assert_eq!(bridge_oracle_client.lastprice(&cross).unwrap().price, 1_9999999);
+    assert_eq!(bridge_oracle_client.price(&cross, &9_600).unwrap().price, 1_9999999);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1404)")]
+fn test_cross_rate_single_leg() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+
+    bridge_oracle_client.set_cross_rate(
+        &Asset::Other(Symbol::new(&env, "EURXLM")),
+        &svec![&env, CrossLeg { asset: euro_asset, inverse: false }],
+    );
+}
+
+#[test]
+fn test_fixed_prices() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+    let asset = Asset::Stellar(token.clone());
+    let usd_asset = Asset::Other(Symbol::new(&env, "USD"));
+    let basket_asset = Asset::Other(Symbol::new(&env, "BASKET"));
+
+    // USD is registered on construction
+    let usd = bridge_oracle_client.fixed_price(&usd_asset).unwrap();
+    assert_eq!((usd.price, usd.decimals), (1, 0));
+    bridge_oracle_client.add_asset(&asset, &usd_asset);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_0000000);
+    assert_eq!(price.timestamp, 10_000);
+
+    bridge_oracle_client.set_fixed_price(&basket_asset, &1_23456789, &8);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_fixed_price"),
+                    (basket_asset.clone(), 1_23456789_i128, 8_u32).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    bridge_oracle_client.add_asset(&asset, &basket_asset);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_2345679);
+    assert_eq!(bridge_oracle_client.price(&asset, &9_000).unwrap().price, 1_2345679);
+
+    // without a fixed price the asset is priced by the other oracle
+    bridge_oracle_client.remove_fixed_price(&basket_asset);
+    assert!(bridge_oracle_client.fixed_price(&basket_asset).is_none());
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1405)")]
+fn test_fixed_price_not_positive() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+
+    bridge_oracle_client.set_fixed_price(&Asset::Other(Symbol::new(&env, "BASKET")), &0, &7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1405)")]
+fn test_fixed_price_too_many_decimals() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+
+    bridge_oracle_client.set_fixed_price(&Asset::Other(Symbol::new(&env, "BASKET")), &1, &39);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1405)")]
+fn test_fixed_price_overflows_decimals() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 7, 14);
+
+    // scaling up to the 7 decimals of the bridge oracle overflows
+    bridge_oracle_client.set_fixed_price(&Asset::Other(Symbol::new(&env, "BASKET")), &(i128::MAX / 10), &0);
+}
+
+#[test]
+fn test_breaker() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(20_000);
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&guardian);
+    assert_eq!(bridge_oracle_client.guardian(), Some(guardian.clone()));
+
+    bridge_oracle_client.set_breaker(&asset, &0_2000000, &3_600);
+    let breaker = bridge_oracle_client.breaker(&asset).unwrap();
+    assert_eq!((breaker.max_deviation, breaker.window), (0_2000000, 3_600));
+
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+    // reading a price does not record it
+    assert!(bridge_oracle_client.last_good_price(&asset).is_none());
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert_eq!(bridge_oracle_client.last_good_price(&asset).unwrap().timestamp, 9_900);
+
+    // a 27% move within the window is rejected
+    mock_oracle_client.set_price(&svec![&env, 1_40000000000000], &10_200);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    assert_eq!(
+        bridge_oracle_client.try_lastprice_strict(&asset).err(),
+        Some(Ok(BridgeOracleError::PriceDeviationError.into()))
+    );
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert_eq!(bridge_oracle_client.last_good_price(&asset).unwrap().price, 1_10000000000000);
+
+    // a move within the max deviation is accepted
+    mock_oracle_client.set_price(&svec![&env, 1_30000000000000], &10_500);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_30000000000000);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert_eq!(bridge_oracle_client.last_good_price(&asset).unwrap().timestamp, 10_500);
+
+    // a move after the window is accepted
+    mock_oracle_client.set_price(&svec![&env, 2_00000000000000], &14_400);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 2_00000000000000);
+
+    bridge_oracle_client.set_breaker(&asset, &0, &0);
+    assert!(bridge_oracle_client.breaker(&asset).is_none());
+}
+
+#[test]
+fn test_freeze() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(20_000);
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&guardian);
+    bridge_oracle_client.set_breaker(&asset, &0_2000000, &3_600);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+
+    bridge_oracle_client.freeze(&asset);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            guardian.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "freeze"),
+                    (asset.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert!(bridge_oracle_client.frozen(&asset));
+
+    // the last accepted price is returned while frozen
+    mock_oracle_client.set_price(&svec![&env, 1_15000000000000], &19_800);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!((price.price, price.timestamp), (1_10000000000000, 9_900));
+
+    bridge_oracle_client.unfreeze(&asset);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "unfreeze"),
+                    (asset.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert!(!bridge_oracle_client.frozen(&asset));
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_15000000000000);
+}
+
+#[test]
+fn test_freeze_without_breaker() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(20_000);
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&guardian);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert_eq!(bridge_oracle_client.last_good_price(&asset).unwrap().price, 1_10000000000000);
+
+    // the last valid price is returned while frozen, even without a circuit breaker
+    bridge_oracle_client.freeze(&asset);
+    mock_oracle_client.set_price(&svec![&env, 1_15000000000000], &19_800);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!((price.price, price.timestamp), (1_10000000000000, 9_900));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1407)")]
+fn test_freeze_without_guardian() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+
+    bridge_oracle_client.freeze(&Asset::Other(Symbol::new(&env, "EURO")));
+}
+
+#[test]
+fn test_assets_upkeep() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let token = Asset::Stellar(Address::generate(&env));
+    let euro = Asset::Other(Symbol::new(&env, "EURO"));
+    let usd = Asset::Other(Symbol::new(&env, "USD"));
+    let legs = svec![&env, CrossLeg { asset: euro.clone(), inverse: false }, CrossLeg { asset: usd.clone(), inverse: true }];
+
+    // an asset stays listed while any of its pricing sources is left
+    bridge_oracle_client.add_asset(&token, &euro);
+    bridge_oracle_client.set_cross_rate(&token, &legs);
+    assert!(bridge_oracle_client.assets() == svec![&env, token.clone()]);
+    bridge_oracle_client.remove_asset(&token);
+    assert!(bridge_oracle_client.assets() == svec![&env, token.clone()]);
+    bridge_oracle_client.set_cross_rate(&token, &svec![&env]);
+    assert_eq!(bridge_oracle_client.assets().len(), 0);
+
+    // routing the quoted asset does not list it, and removing the route leaves the mappings
+    bridge_oracle_client.add_asset(&token, &euro);
+    bridge_oracle_client.set_route(&euro, &bridge_oracle_client.other_oracle());
+    assert!(bridge_oracle_client.assets() == svec![&env, token.clone()]);
+    bridge_oracle_client.remove_route(&euro);
+    assert!(bridge_oracle_client.assets() == svec![&env, token.clone()]);
+    bridge_oracle_client.remove_asset(&token);
+    assert_eq!(bridge_oracle_client.assets().len(), 0);
+}
+
+#[test]
+fn test_routes() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let euro = Asset::Other(Symbol::new(&env, "EURO"));
+    bridge_oracle_client.add_asset(&asset, &euro);
+    mock_oracle_client.set_price(&svec![&env, 1_08000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.route(&euro), None);
+
+    // the bridge asset is routed to another oracle, and its price is rescaled
+    let euro_oracle = create_euro_source(&env, &admin, 7, 1_1000000, 9_900);
+    bridge_oracle_client.set_route(&euro, &euro_oracle);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_route"),
+                    (euro.clone(), euro_oracle.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.route(&euro), Some(euro_oracle.clone()));
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset), svec![&env, euro_oracle.clone()]);
+    // the route prices the quoted asset, which is listed through the asset mapped to it
+    assert!(bridge_oracle_client.assets() == svec![&env, asset.clone()]);
+
+    // without the route the default route of the variant is used again
+    bridge_oracle_client.remove_route(&euro);
+    assert_eq!(bridge_oracle_client.route(&euro), None);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_08000000000000);
+}
+
+#[test]
+fn test_set_oracles() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (mock_stellar_oracle_client, mock_oracle_client, bridge_oracle_client) =
+        create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    mock_oracle_client.set_price(&svec![&env, 1_08000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_08000000000000);
+
+    let new_other_oracle = create_euro_source(&env, &admin, 14, 1_12000000000000, 9_900);
+    bridge_oracle_client.set_oracles(&mock_stellar_oracle_client.address, &new_other_oracle);
+    assert_eq!(bridge_oracle_client.stellar_oracle(), mock_stellar_oracle_client.address);
+    assert_eq!(bridge_oracle_client.other_oracle(), new_other_oracle);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_12000000000000);
+    // decimals are stored when the oracles are set, so prices are rescaled without asking for them
+    env.as_contract(&bridge_oracle_client.address, || {
+        let key = BridgeOracleDataKey::DECIMALS(new_other_oracle.clone());
+        assert_eq!(env.storage().instance().get::<_, u32>(&key), Some(14));
+        let key = BridgeOracleDataKey::DECIMALS(mock_stellar_oracle_client.address.clone());
+        assert_eq!(env.storage().instance().get::<_, u32>(&key), Some(14));
+    });
+}
+
+fn mapping_assets(env: &Env, page: &soroban_sdk::Vec<Mapping>, index: u32) -> soroban_sdk::Vec<Asset> {
+    let mapping = page.get_unchecked(index);
+    svec![env, mapping.asset, mapping.to]
+}
+
+#[test]
+fn test_remove_asset_and_mappings() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let euro = Asset::Other(Symbol::new(&env, "EURO"));
+    let usd = Asset::Other(Symbol::new(&env, "USD"));
+    let token1 = Asset::Stellar(Address::generate(&env));
+    let token2 = Asset::Stellar(Address::generate(&env));
+    let token3 = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.add_asset(&token1, &euro);
+    bridge_oracle_client.add_asset(&token2, &usd);
+    bridge_oracle_client.add_asset(&token3, &euro);
+    // overwriting a mapping keeps its position
+    bridge_oracle_client.add_asset(&token1, &usd);
+
+    let page = bridge_oracle_client.mappings(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert!(mapping_assets(&env, &page, 0) == svec![&env, token1.clone(), usd.clone()]);
+    assert!(mapping_assets(&env, &page, 1) == svec![&env, token2.clone(), usd.clone()]);
+    let page = bridge_oracle_client.mappings(&2, &2);
+    assert_eq!(page.len(), 1);
+    assert!(mapping_assets(&env, &page, 0) == svec![&env, token3.clone(), euro.clone()]);
+    assert_eq!(bridge_oracle_client.mappings(&3, &2).len(), 0);
+
+    bridge_oracle_client.remove_asset(&token2);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "remove_asset"),
+                    (token2.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    // the last mapping moves into the position of the removed one
+    let page = bridge_oracle_client.mappings(&0, &10);
+    assert_eq!(page.len(), 2);
+    assert!(mapping_assets(&env, &page, 0) == svec![&env, token1.clone(), usd.clone()]);
+    assert!(mapping_assets(&env, &page, 1) == svec![&env, token3.clone(), euro.clone()]);
+    assert!(!bridge_oracle_client.assets().contains(&token2));
+    assert!(bridge_oracle_client.assets().contains(&token3));
+    // each mapping is kept in its own persistent entry
+    env.as_contract(&bridge_oracle_client.address, || {
+        assert!(env.storage().persistent().has(&BridgeOracleDataKey::MAPPING(1)));
+        assert!(!env.storage().persistent().has(&BridgeOracleDataKey::MAPPING(2)));
+        assert!(!env.storage().persistent().has(&BridgeOracleDataKey::MAPPINGINDEX(token2.clone())));
+    });
+
+    bridge_oracle_client.remove_asset(&token3);
+    bridge_oracle_client.remove_asset(&token2);
+    let page = bridge_oracle_client.mappings(&0, &10);
+    assert_eq!(page.len(), 1);
+    assert!(mapping_assets(&env, &page, 0) == svec![&env, token1.clone(), usd.clone()]);
+    bridge_oracle_client.add_asset(&token2, &euro);
+    let page = bridge_oracle_client.mappings(&0, &10);
+    assert_eq!(page.len(), 2);
+    assert!(mapping_assets(&env, &page, 1) == svec![&env, token2.clone(), euro.clone()]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1410)")]
+fn test_add_asset_to_itself() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let euro = Asset::Other(Symbol::new(&env, "EURO"));
+
+    bridge_oracle_client.add_asset(&euro, &euro);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1410)")]
+fn test_add_asset_cycle() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let euro = Asset::Other(Symbol::new(&env, "EURO"));
+    let token = Asset::Stellar(Address::generate(&env));
+    bridge_oracle_client.add_asset(&token, &euro);
+    bridge_oracle_client.set_fixed_price(&token, &1_1000000, &7);
+
+    bridge_oracle_client.add_asset(&euro, &token);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1410)")]
+fn test_add_asset_not_quoted() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let token = Asset::Stellar(Address::generate(&env));
+
+    bridge_oracle_client.add_asset(&token, &Asset::Other(Symbol::new(&env, "GBP")));
+}
+
+#[test]
+fn test_override() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&guardian);
+    bridge_oracle_client.set_override_band(&0_1000000);
+    assert_eq!(bridge_oracle_client.override_band(), Some(0_1000000));
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+
+    bridge_oracle_client.set_override(&asset, &1_05000000000000, &12_000);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            guardian.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_override"),
+                    (asset.clone(), 1_05000000000000_i128, 12_000_u64).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.price_override(&asset).unwrap().expires_at, 12_000);
+
+    // the override is returned while it is active
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!((price.price, price.timestamp), (1_05000000000000, 10_000));
+    let event = OverrideUsed { version: EVENT_VERSION, asset: asset.clone(), price: 1_05000000000000 };
+    assert_eq!(
+        env.events().all(),
+        svec![&env, (bridge_oracle_client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(bridge_oracle_client.lastprice_sources(&asset).len(), 0);
+
+    // the override expires
+    env.ledger().set_timestamp(12_000);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+
+    // the admin can remove an active override
+    bridge_oracle_client.set_override(&asset, &1_05000000000000, &13_000);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_05000000000000);
+    bridge_oracle_client.remove_override(&asset);
+    assert!(bridge_oracle_client.price_override(&asset).is_none());
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1411)")]
+fn test_override_outside_band() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&Address::generate(&env));
+    bridge_oracle_client.set_override_band(&0_1000000);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+
+    bridge_oracle_client.set_override(&asset, &0_98000000000000, &12_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1411)")]
+fn test_override_without_last_good_price() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&Address::generate(&env));
+    bridge_oracle_client.set_override_band(&0_1000000);
+    // the live price is not used as the anchor of the band
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+
+    bridge_oracle_client.set_override(&asset, &1_10000000000000, &12_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1413)")]
+fn test_override_out_of_bounds() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&Address::generate(&env));
+    bridge_oracle_client.set_override_band(&0_1000000);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    bridge_oracle_client.set_bounds(&asset, &1_0000000, &1_1200000);
+
+    // within the band, but above the max of the asset
+    bridge_oracle_client.set_override(&asset, &1_15000000000000, &12_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1411)")]
+fn test_override_expired() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_guardian(&Address::generate(&env));
+    bridge_oracle_client.set_override_band(&0_1000000);
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_900);
+
+    bridge_oracle_client.set_override(&asset, &1_10000000000000, &10_000);
+}
+
+#[test]
+fn test_bounds() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    bridge_oracle_client.set_bounds(&asset, &0_8000000, &1_6000000);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_bounds"),
+                    (asset.clone(), 0_8000000_i128, 1_6000000_i128).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.bounds(&asset).unwrap().max, 1_6000000);
+
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_600);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+
+    // a price quoted with the wrong decimals is rejected
+    mock_oracle_client.set_price(&svec![&env, 110_00000000000000], &9_900);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    let event = PriceOutOfBounds {
+        version: EVENT_VERSION,
+        asset: asset.clone(),
+        price: 110_00000000000000,
+        min: 0_80000000000000,
+        max: 1_60000000000000,
+    };
+    assert_eq!(
+        env.events().all(),
+        svec![&env, (bridge_oracle_client.address.clone(), event.topics(&env), event.data(&env))]
+    );
+    assert_eq!(
+        bridge_oracle_client.try_lastprice_strict(&asset).err(),
+        Some(Ok(BridgeOracleError::PriceOutOfBoundsError.into()))
+    );
+
+    bridge_oracle_client.remove_bounds(&asset);
+    assert!(bridge_oracle_client.bounds(&asset).is_none());
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 110_00000000000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1412)")]
+fn test_bounds_max_below_min() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+
+    bridge_oracle_client.set_bounds(&Asset::Other(Symbol::new(&env, "EURO")), &1_6000000, &0_8000000);
+}
+
+/// A liquid staking token quoting its exchange rate with 7 decimals
+#[contract]
+pub struct MockStakedToken;
+
+#[contractimpl]
+impl MockStakedToken {
+    pub fn set_rate(e: Env, rate: i128) {
+        e.storage().instance().set(&symbol_short!("RATE"), &rate);
+    }
+
+    pub fn exchange_rate(e: Env) -> i128 {
+        e.storage().instance().get(&symbol_short!("RATE")).unwrap()
+    }
+}
+
+#[test]
+fn test_ratio() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_600);
+    mock_oracle_client.set_price(&svec![&env, 1_20000000000000], &9_900);
+
+    // a fixed ratio
+    let rate = Rate::Fixed(1_0500000);
+    bridge_oracle_client.set_ratio(&asset, &rate, &7);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_ratio"),
+                    (asset.clone(), rate.clone(), 7_u32).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.ratio(&asset).unwrap().decimals, 7);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_26000000000000);
+    let prices = bridge_oracle_client.prices(&asset, &2).unwrap();
+    assert_eq!(prices.get_unchecked(1).price, 1_15500000000000);
+    assert_eq!(bridge_oracle_client.price(&asset, &9_600).unwrap().price, 1_15500000000000);
+
+    // a rate from a contract
+    let staked_token = env.register(MockStakedToken, ());
+    let staked_token_client = MockStakedTokenClient::new(&env, &staked_token);
+    staked_token_client.set_rate(&1_2000000);
+    bridge_oracle_client.set_ratio(&asset, &Rate::Contract(staked_token.clone(), Symbol::new(&env, "exchange_rate")), &7);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_44000000000000);
+    staked_token_client.set_rate(&1_2500000);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_50000000000000);
+
+    // no price is returned if the rate is invalid
+    staked_token_client.set_rate(&0);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+
+    bridge_oracle_client.remove_ratio(&asset);
+    assert!(bridge_oracle_client.ratio(&asset).is_none());
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_20000000000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1414)")]
+fn test_ratio_contract_fails() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let staked_token = env.register(MockStakedToken, ());
+
+    // the rate has not been set, so the call fails
+    bridge_oracle_client.set_ratio(
+        &Asset::Stellar(token),
+        &Rate::Contract(staked_token, Symbol::new(&env, "exchange_rate")),
+        &7,
+    );
+}
+
+#[test]
+fn test_cache() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    bridge_oracle_client.add_asset(&asset, &Asset::Other(Symbol::new(&env, "EURO")));
+    assert!(!bridge_oracle_client.cache());
+
+    bridge_oracle_client.set_cache(&true);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_cache"),
+                    (true,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert!(bridge_oracle_client.cache());
+
+    // reading a price does not cache it
+    mock_oracle_client.set_price(&svec![&env, 1_10000000000000], &9_600);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_10000000000000);
+    mock_oracle_client.set_price(&svec![&env, 1_20000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_20000000000000);
+
+    // a poked price is cached for the rest of the ledger
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    mock_oracle_client.set_price(&svec![&env, 1_30000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_20000000000000);
+    assert_eq!(bridge_oracle_client.lastprice_strict(&asset).price, 1_20000000000000);
+
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_30000000000000);
+
+    // disabling the cache reads the oracle again in the same ledger
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    mock_oracle_client.set_price(&svec![&env, 1_40000000000000], &9_900);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_30000000000000);
+    bridge_oracle_client.set_cache(&false);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_40000000000000);
+}
+
+#[test]
+fn test_lastprice_detailed() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, mock_oracle_client, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+    bridge_oracle_client.add_asset(&asset, &euro_asset);
+    mock_oracle_client.set_price(&svec![&env, 1_08000000000000], &9_900);
+
+    let detailed = bridge_oracle_client.lastprice_detailed(&asset).unwrap();
+    assert_eq!(detailed.price.price, 1_08000000000000);
+    assert!(detailed.status == PriceStatus::Valid);
+    assert!(detailed.source == PriceSource::OtherOracle);
+    assert_eq!(detailed.age, 100);
+    assert_eq!(detailed.count, 1);
+    assert_eq!(detailed.spread, 0);
+
+    // stale prices are returned with their status
+    bridge_oracle_client.set_max_age(&asset, &600);
+    env.ledger().set_timestamp(10_600);
+    let detailed = bridge_oracle_client.lastprice_detailed(&asset).unwrap();
+    assert!(detailed.status == PriceStatus::Stale);
+    assert_eq!(detailed.age, 700);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+
+    // the spread of the median sources is relative to the median
+    env.ledger().set_timestamp(10_000);
+    let sources = svec![
+        &env,
+        create_euro_source(&env, &admin, 14, 1_08000000000000, 9_900),
+        create_euro_source(&env, &admin, 7, 1_0900000, 9_600),
+        create_euro_source(&env, &admin, 18, 1_100000000000000000, 9_900),
+    ];
+    bridge_oracle_client.set_price_sources(&euro_asset, &sources, &2, &0_0500000, &600);
+    let detailed = bridge_oracle_client.lastprice_detailed(&asset).unwrap();
+    assert_eq!(detailed.price.price, 1_09000000000000);
+    assert!(detailed.source == PriceSource::Median);
+    assert_eq!(detailed.age, 400);
+    assert_eq!(detailed.count, 3);
+    assert_eq!(detailed.spread, 0_0183486);
+
+    // USD is priced by the fixed path
+    let detailed = bridge_oracle_client.lastprice_detailed(&Asset::Other(Symbol::new(&env, "USD"))).unwrap();
+    assert_eq!(detailed.price.price, 1_00000000000000);
+    assert!(detailed.source == PriceSource::Fixed);
+    assert_eq!(detailed.age, 0);
+    assert_eq!(detailed.count, 0);
+
+    bridge_oracle_client.set_guardian(&Address::generate(&env));
+    bridge_oracle_client.set_override_band(&0_1000000);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    bridge_oracle_client.set_override(&asset, &1_10000000000000, &10_500);
+    let detailed = bridge_oracle_client.lastprice_detailed(&asset).unwrap();
+    assert_eq!(detailed.price.price, 1_10000000000000);
+    assert!(detailed.source == PriceSource::Override);
+    assert_eq!(detailed.count, 0);
+
+    assert!(bridge_oracle_client.lastprice_detailed(&Asset::Other(Symbol::new(&env, "GBP"))).is_none());
+}
+
+/// An oracle following the Reflector interface, which only records prices at multiples of its
+/// resolution and only returns the last price of assets updated in its last round
+#[contract]
+pub struct MockReflector;
+
+#[contractimpl]
+impl MockReflector {
+    pub fn set_data(e: Env, decimals: u32, resolution: u32) {
+        e.storage().instance().set(&symbol_short!("DECIMALS"), &decimals);
+        e.storage().instance().set(&symbol_short!("RES"), &resolution);
+    }
+
+    pub fn set_round(e: Env, prices: soroban_sdk::Vec<(Asset, i128)>, timestamp: u64) {
+        assert_eq!(timestamp % Self::resolution(e.clone()) as u64, 0);
+        for (asset, price) in prices.iter() {
+            e.storage().instance().set(&(asset, timestamp), &price);
+        }
+        e.storage().instance().set(&symbol_short!("LAST"), &timestamp);
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        e.storage().instance().get(&symbol_short!("DECIMALS")).unwrap()
+    }
+
+    pub fn resolution(e: Env) -> u32 {
+        e.storage().instance().get(&symbol_short!("RES")).unwrap()
+    }
+
+    pub fn last_timestamp(e: Env) -> u64 {
+        e.storage().instance().get(&symbol_short!("LAST")).unwrap_or(0)
+    }
+
+    pub fn price(e: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        let price: i128 = e.storage().instance().get(&(asset, timestamp))?;
+        Some(PriceData { price, timestamp })
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        let timestamp = Self::last_timestamp(e.clone());
+        Self::price(e, asset, timestamp)
+    }
+
+    pub fn prices(e: Env, asset: Asset, records: u32) -> Option<soroban_sdk::Vec<PriceData>> {
+        let resolution = Self::resolution(e.clone()) as u64;
+        let mut timestamp = Self::last_timestamp(e.clone());
+        let mut prices = soroban_sdk::Vec::new(&e);
+        for _ in 0..records {
+            if let Some(price) = Self::price(e.clone(), asset.clone(), timestamp) {
+                prices.push_back(price);
+            }
+            match timestamp.checked_sub(resolution) {
+                Some(previous) => timestamp = previous,
+                None => break,
+            }
+        }
+        if prices.is_empty() {
+            None
+        } else {
+            Some(prices)
+        }
+    }
+}
+
+#[test]
+fn test_reflector_adapter() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+    bridge_oracle_client.add_asset(&asset, &euro_asset);
+
+    let reflector = env.register(MockReflector, ());
+    let reflector_client = MockReflectorClient::new(&env, &reflector);
+    reflector_client.set_data(&8, &300);
+    reflector_client.set_round(&svec![&env, (euro_asset.clone(), 1_08000000)], &9_600);
+    // EURO was not updated in the last round
+    reflector_client.set_round(&svec![&env], &9_900);
+    bridge_oracle_client.set_route(&euro_asset, &reflector);
+    assert!(bridge_oracle_client.adapter(&reflector) == Adapter::Sep40);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+    assert!(bridge_oracle_client.price(&asset, &9_700).is_none());
+
+    bridge_oracle_client.set_adapter(&reflector, &Adapter::Reflector);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_adapter"),
+                    (reflector.clone(), Adapter::Reflector).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert!(bridge_oracle_client.adapter(&reflector) == Adapter::Reflector);
+
+    // the price is read from the round before and rescaled from 8 decimals
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_08000000000000);
+    assert_eq!(price.timestamp, 9_600);
+
+    // timestamps are rounded down to the round they fall in
+    assert_eq!(bridge_oracle_client.price(&asset, &9_700).unwrap().price, 1_08000000000000);
+    assert_eq!(bridge_oracle_client.prices(&asset, &2).unwrap().len(), 1);
+
+    reflector_client.set_round(&svec![&env, (euro_asset.clone(), 1_09000000)], &10_200);
+    env.ledger().set_timestamp(10_300);
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 1_09000000000000);
+    assert_eq!(price.timestamp, 10_200);
+
+    bridge_oracle_client.set_adapter(&reflector, &Adapter::Sep40);
+    assert!(bridge_oracle_client.adapter(&reflector) == Adapter::Sep40);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1416)")]
+fn test_reflector_adapter_not_reflector() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let staked_token = env.register(MockStakedToken, ());
+
+    bridge_oracle_client.set_adapter(&staked_token, &Adapter::Reflector);
+}
+
+#[test]
+fn test_inverse_mapping() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let jpy_asset = Asset::Other(Symbol::new(&env, "JPY"));
+
+    // the feed quotes yen per USD
+    let (jpy_oracle, jpy_oracle_client) = create_mock_oracle(&env);
+    jpy_oracle_client.set_data(
+        &admin,
+        &TestAsset::Other(Symbol::new(&env, "USD")),
+        &svec![&env, TestAsset::Other(Symbol::new(&env, "JPY"))],
+        &7,
+        &300,
+    );
+    jpy_oracle_client.set_price(&svec![&env, 150_0000000], &9_600);
+    jpy_oracle_client.set_price(&svec![&env, 151_2345678], &9_900);
+    bridge_oracle_client.set_route(&jpy_asset, &jpy_oracle);
+
+    bridge_oracle_client.add_inverse_asset(&asset, &jpy_asset);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "add_inverse_asset"),
+                    (asset.clone(), jpy_asset.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let mappings = bridge_oracle_client.mappings(&0, &10);
+    assert_eq!(mappings.len(), 1);
+    assert!(mappings.get_unchecked(0).inverse);
+
+    // 1 / 151.2345678 in 14 decimals, rounded down
+    let price = bridge_oracle_client.lastprice(&asset).unwrap();
+    assert_eq!(price.price, 0_00661224490238);
+    assert_eq!(price.timestamp, 9_900);
+    assert_eq!(bridge_oracle_client.price(&asset, &9_600).unwrap().price, 0_00666666666666);
+    let prices = bridge_oracle_client.prices(&asset, &2).unwrap();
+    assert_eq!(prices.get_unchecked(0).price, 0_00661224490238);
+    assert_eq!(prices.get_unchecked(1).price, 0_00666666666666);
+
+    // the quoted asset itself is not inverted
+    assert_eq!(bridge_oracle_client.lastprice(&jpy_asset).unwrap().price, 151_23456780000000);
+
+    // a price that can't be inverted is not returned
+    jpy_oracle_client.set_price(&svec![&env, 0], &9_900);
+    assert!(bridge_oracle_client.lastprice(&asset).is_none());
+
+    // mapping the asset again is not inverse
+    jpy_oracle_client.set_price(&svec![&env, 151_2345678], &9_900);
+    bridge_oracle_client.add_asset(&asset, &jpy_asset);
+    assert!(!bridge_oracle_client.mappings(&0, &10).get_unchecked(0).inverse);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 151_23456780000000);
+}
+
+/// An oracle that only quotes the last price of its assets and keeps no history
+#[contract]
+pub struct MockSpotOracle;
+
+#[contractimpl]
+impl MockSpotOracle {
+    pub fn set_data(e: Env, assets: soroban_sdk::Vec<Asset>, decimals: u32) {
+        e.storage().instance().set(&symbol_short!("ASSETS"), &assets);
+        e.storage().instance().set(&symbol_short!("DECIMALS"), &decimals);
+    }
+
+    pub fn set_price(e: Env, asset: Asset, price: i128, timestamp: u64) {
+        e.storage().instance().set(&asset, &PriceData { price, timestamp });
+    }
+
+    pub fn assets(e: Env) -> soroban_sdk::Vec<Asset> {
+        e.storage().instance().get(&symbol_short!("ASSETS")).unwrap()
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        e.storage().instance().get(&symbol_short!("DECIMALS")).unwrap()
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        e.storage().instance().get(&asset)
+    }
+
+    pub fn prices(_e: Env, _asset: Asset, _records: u32) -> Option<soroban_sdk::Vec<PriceData>> {
+        None
+    }
+}
+
+#[test]
+fn test_history() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    let asset = Asset::Stellar(token.clone());
+    let euro_asset = Asset::Other(Symbol::new(&env, "EURO"));
+
+    let spot_oracle = env.register(MockSpotOracle, ());
+    let spot_oracle_client = MockSpotOracleClient::new(&env, &spot_oracle);
+    spot_oracle_client.set_data(&svec![&env, euro_asset.clone()], &14);
+    spot_oracle_client.set_price(&euro_asset, &1_00000000000000, &10_000);
+    bridge_oracle_client.set_route(&euro_asset, &spot_oracle);
+    bridge_oracle_client.add_asset(&asset, &euro_asset);
+
+    // nothing is recorded until a size is set
+    assert_eq!(bridge_oracle_client.history_size(), 0);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert!(bridge_oracle_client.prices(&asset, &3).is_none());
+    assert!(bridge_oracle_client.history_twap(&asset, &3).is_none());
+
+    bridge_oracle_client.set_history_size(&3);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    bridge_oracle_client.address.clone(),
+                    Symbol::new(&env, "set_history_size"),
+                    (3u32,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(bridge_oracle_client.history_size(), 3);
+
+    // prices are recorded when they are poked, once per timestamp
+    for (index, price) in [1_00000000000000i128, 1_10000000000000, 1_20000000000000, 1_30000000000000].iter().enumerate() {
+        let timestamp = 10_000 + 300 * index as u64;
+        env.ledger().set_timestamp(timestamp);
+        spot_oracle_client.set_price(&euro_asset, price, &timestamp);
+        bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+        bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    }
+
+    // reading a price does not record it
+    env.ledger().set_timestamp(11_000);
+    spot_oracle_client.set_price(&euro_asset, &1_35000000000000, &11_000);
+    assert_eq!(bridge_oracle_client.lastprice(&asset).unwrap().price, 1_35000000000000);
+    spot_oracle_client.set_price(&euro_asset, &1_30000000000000, &10_900);
+    let prices = bridge_oracle_client.prices(&asset, &5).unwrap();
+    assert_eq!(prices.len(), 3);
+    assert_eq!(prices.get_unchecked(0).price, 1_30000000000000);
+    assert_eq!(prices.get_unchecked(0).timestamp, 10_900);
+    assert_eq!(prices.get_unchecked(1).price, 1_20000000000000);
+    assert_eq!(prices.get_unchecked(1).timestamp, 10_600);
+    assert_eq!(prices.get_unchecked(2).price, 1_10000000000000);
+    assert_eq!(prices.get_unchecked(2).timestamp, 10_300);
+    assert_eq!(bridge_oracle_client.prices(&asset, &1).unwrap().len(), 1);
+
+    // each price is weighted by the time until the next one, and the last until now
+    env.ledger().set_timestamp(11_200);
+    let twap = bridge_oracle_client.history_twap(&asset, &3).unwrap();
+    assert_eq!(twap.price, 1_20000000000000);
+    assert_eq!(twap.timestamp, 10_900);
+    assert_eq!(bridge_oracle_client.history_twap(&asset, &1).unwrap().price, 1_30000000000000);
+
+    // shrinking the history keeps the newest prices
+    bridge_oracle_client.set_history_size(&2);
+    spot_oracle_client.set_price(&euro_asset, &1_40000000000000, &11_200);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    let prices = bridge_oracle_client.prices(&asset, &5).unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices.get_unchecked(0).price, 1_40000000000000);
+    assert_eq!(prices.get_unchecked(1).price, 1_30000000000000);
+
+    // growing the history keeps them in order
+    bridge_oracle_client.set_history_size(&4);
+    env.ledger().set_timestamp(11_500);
+    spot_oracle_client.set_price(&euro_asset, &1_50000000000000, &11_500);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    let prices = bridge_oracle_client.prices(&asset, &5).unwrap();
+    assert_eq!(prices.len(), 3);
+    assert_eq!(prices.get_unchecked(0).price, 1_50000000000000);
+    assert_eq!(prices.get_unchecked(1).price, 1_40000000000000);
+    assert_eq!(prices.get_unchecked(2).price, 1_30000000000000);
+
+    // stopping the history keeps the recorded prices
+    bridge_oracle_client.set_history_size(&0);
+    env.ledger().set_timestamp(11_800);
+    spot_oracle_client.set_price(&euro_asset, &1_60000000000000, &11_800);
+    bridge_oracle_client.poke(&svec![&env, asset.clone()]);
+    assert_eq!(bridge_oracle_client.prices(&asset, &5).unwrap().get_unchecked(0).price, 1_50000000000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1417)")]
+fn test_history_size_too_large() {
+    let env: Env = Default::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_, _, bridge_oracle_client) = create_bridge_oracle_with_decimals(&env, &admin, 14, 14);
+    bridge_oracle_client.set_history_size(&257);
+}
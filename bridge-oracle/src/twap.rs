@@ -0,0 +1,181 @@
+use sep_40_oracle::Asset;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contracttype, token::TokenClient, vec, Address, Env, Vec};
+use crate::constants::SCALAR_7;
+use crate::{price, storage};
+
+/// The max time between two observations, as a fraction of the window. The bundled Soroswap pair
+/// exposes no price cumulatives, so the spot price at each observation is assumed to hold until the
+/// next one, and a longer gap would let a single skewed observation dominate the average
+pub const MAX_GAP_FRACTION: u64 = 4;
+
+/// The min number of observations within the window for the average to be used
+pub const MIN_SAMPLES: u32 = 4;
+
+/// The max move of an observed spot price from the running average, with 7 decimals. A pair
+/// skewed and restored within a single transaction can only pull each observation this far, so
+/// a sustained move is followed over several windows instead
+pub const MAX_STEP: i128 = 0_1000000;
+
+/// A time-weighted average price source for a stellar asset, from a Soroswap pair. The pair
+/// exposes no price cumulatives, so the bridge oracle accumulates the spot price itself each time
+/// the asset is observed, and the average is only as reliable as the observations are frequent
+#[derive(Clone)]
+#[contracttype]
+pub struct TwapConfig {
+    /// The Soroswap pair of the asset and the token it is quoted in
+    pub pair: Address,
+    /// The time window in seconds the price is averaged over
+    pub window: u64,
+    /// The min reserve of the quote token in the pair
+    pub min_liquidity: i128,
+}
+
+/// A cumulative price observation
+#[derive(Clone)]
+#[contracttype]
+pub struct Observation {
+    pub timestamp: u64,
+    pub cumulative: i128,
+}
+
+/// The cumulative price of an asset in its pair, accumulated by the bridge oracle
+#[derive(Clone)]
+#[contracttype]
+pub struct TwapState {
+    /// The spot price at the last observation, within the max step of the average, in the
+    /// decimals of the bridge oracle
+    pub spot: i128,
+    /// The timestamp of the last observation
+    pub timestamp: u64,
+    /// The sum of the spot price over each second up to the last observation
+    pub cumulative: i128,
+    /// The observations within the window, oldest first
+    pub observations: Vec<Observation>,
+}
+
+/// The token the asset is quoted in by the pair, and the reserves of the asset and the quote token
+fn reserves(env: &Env, token: &Address, pair: &Address) -> Option<(Address, i128, i128)> {
+    let token_0 = price::try_invoke::<Address>(env, pair, "token_0", vec![env])?;
+    let token_1 = price::try_invoke::<Address>(env, pair, "token_1", vec![env])?;
+    let (reserve_0, reserve_1) = price::try_invoke::<(i128, i128)>(env, pair, "get_reserves", vec![env])?;
+    if *token == token_0 {
+        Some((token_1, reserve_0, reserve_1))
+    } else if *token == token_1 {
+        Some((token_0, reserve_1, reserve_0))
+    } else {
+        None
+    }
+}
+
+/// Fetch the token the asset is quoted in by the pair
+/// # Arguments
+/// * `asset` - The asset to fetch the quote token for
+/// * `pair` - The Soroswap pair of the asset
+pub fn quote_token(env: &Env, asset: &Asset, pair: &Address) -> Option<Address> {
+    match asset {
+        Asset::Stellar(token) => reserves(env, token, pair).map(|(quote, _, _)| quote),
+        Asset::Other(_) => None,
+    }
+}
+
+/// The token the asset is quoted in by the pair and the spot price of the asset, or None if the
+/// pair has less than the min liquidity
+fn spot(env: &Env, asset: &Asset, config: &TwapConfig) -> Option<(Address, i128)> {
+    let token = match asset {
+        Asset::Stellar(token) => token,
+        Asset::Other(_) => return None,
+    };
+    let (quote, reserve, quote_reserve) = reserves(env, token, &config.pair)?;
+    if quote_reserve < config.min_liquidity || reserve <= 0 {
+        return None;
+    }
+    let decimals = price::decimals(env);
+    let scalar = 10i128.pow(decimals);
+    let reserve = price::rescale(reserve, TokenClient::new(env, token).decimals(), decimals);
+    let quote_reserve = price::rescale(quote_reserve, TokenClient::new(env, &quote).decimals(), decimals);
+    Some((quote, quote_reserve.fixed_div_floor(env, &reserve, &scalar)))
+}
+
+/// The stored state of the asset accumulated up to now at the last observed spot price, or None
+/// if the last observation is more than a quarter of the window old
+fn accumulate(env: &Env, asset: &Asset, config: &TwapConfig) -> Option<TwapState> {
+    let now = env.ledger().timestamp();
+    let max_gap = (config.window / MAX_GAP_FRACTION).max(1);
+    let mut state = storage::get_twap_state(env, asset)?;
+    if now.saturating_sub(state.timestamp) > max_gap {
+        return None;
+    }
+    if now > state.timestamp {
+        state.cumulative += state.spot * (now - state.timestamp) as i128;
+        state.timestamp = now;
+
+        // keep the observations at least an eighth of the window apart
+        let last = state.observations.last_unchecked();
+        if now - last.timestamp >= (config.window / 8).max(1) {
+            state.observations.push_back(Observation { timestamp: now, cumulative: state.cumulative });
+        }
+        // drop observations that are no longer needed to cover the window
+        while state.observations.len() > 1
+            && state.observations.get_unchecked(1).timestamp <= now.saturating_sub(config.window)
+        {
+            state.observations.pop_front();
+        }
+    }
+    Some(state)
+}
+
+/// The average price over the observations, or None if they do not cover the window yet or are
+/// too few
+fn average(state: &TwapState, config: &TwapConfig) -> Option<i128> {
+    let oldest = state.observations.first_unchecked();
+    let elapsed = state.timestamp - oldest.timestamp;
+    if elapsed < config.window || elapsed == 0 || state.observations.len() < MIN_SAMPLES {
+        return None;
+    }
+    Some((state.cumulative - oldest.cumulative) / elapsed as i128)
+}
+
+/// Record an observation of the spot price of the asset in its pair
+/// # Arguments
+/// * `asset` - The asset to observe
+/// * `config` - The TWAP source of the asset
+///
+/// Returns the token the asset is quoted in and the updated state, or None if the pair
+/// has less than the min liquidity. Observations are restarted if the last one is more than a
+/// quarter of the window old, and once they cover the window, the spot price is clamped to
+/// within the max step of the average
+pub fn observe(env: &Env, asset: &Asset, config: &TwapConfig) -> Option<(Address, TwapState)> {
+    let (quote, spot) = spot(env, asset, config)?;
+    let now = env.ledger().timestamp();
+    let mut state = accumulate(env, asset, config).unwrap_or_else(|| TwapState {
+        spot,
+        timestamp: now,
+        cumulative: 0,
+        observations: vec![env, Observation { timestamp: now, cumulative: 0 }],
+    });
+    state.spot = match average(&state, config) {
+        Some(average) => {
+            let max_step = average.fixed_mul_floor(env, &MAX_STEP, &SCALAR_7);
+            spot.clamp(average - max_step, average + max_step)
+        }
+        None => spot,
+    };
+    storage::set_twap_state(env, asset, &state);
+    Some((quote, state))
+}
+
+/// Compute the time-weighted average price of the asset in the quote token from its observations.
+/// The pair is not observed, so the spot price at the time of the call takes no part in the average
+/// # Arguments
+/// * `asset` - The asset to fetch the price for
+/// * `config` - The TWAP source of the asset
+///
+/// Returns the token the asset is quoted in and the average price, or None if the pair has
+/// less than the min liquidity, or the observations are stale, do not cover the window yet or
+/// are too few
+pub fn lastprice(env: &Env, asset: &Asset, config: &TwapConfig) -> Option<(Address, i128)> {
+    let (quote, _) = spot(env, asset, config)?;
+    let state = accumulate(env, asset, config)?;
+    Some((quote, average(&state, config)?))
+}
@@ -1,6 +1,7 @@
 use soroban_sdk::{contract, contractclient, contractimpl, Address, Env};
 use crate::dependencies::treasury::{Client as TreasuryClient};
 use crate::dependencies::bridge_oracle::{Client as BridgeOracleClient, Asset};
+use crate::events::{self, EVENT_VERSION};
 #[contract]
 pub struct DaoUtilsContract;
 
@@ -25,15 +26,19 @@ impl DaoUtils for DaoUtilsContract {
         bridge_oracle.add_asset(&token_asset, &asset);
         treasury_client.add_stablecoin(&token, &blend_pool);
         treasury_client.increase_supply(&token, &initial_supply);
+
+        events::NewStablecoin { token, pool: blend_pool, version: EVENT_VERSION, treasury, oracle, asset, initial_supply }.publish(&e);
     }
 
     fn update_supply(e: Env, admin: Address, treasury: Address, token: Address, amount: i128) {
         admin.require_auth();
-        let treasury = TreasuryClient::new(&e, &treasury);
+        let treasury_client = TreasuryClient::new(&e, &treasury);
         if amount > 0 {
-            treasury.increase_supply(&token, &amount);
+            treasury_client.increase_supply(&token, &amount);
         } else {
-            treasury.decrease_supply(&token, &amount.abs());
+            treasury_client.decrease_supply(&token, &amount.abs());
         }
+
+        events::UpdateSupply { token, version: EVENT_VERSION, treasury, amount }.publish(&e);
     }
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+use crate::dependencies::bridge_oracle::Asset;
+use soroban_sdk::{contractevent, Address};
+
+/// The version of the dao utils event schema
+pub const EVENT_VERSION: u32 = 1;
+
+/// Emitted when a new stablecoin is registered and supplied to its blend pool
+#[contractevent(topics = ["DaoUtils", "new_stablecoin"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewStablecoin {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+    pub treasury: Address,
+    pub oracle: Address,
+    pub asset: Asset,
+    pub initial_supply: i128,
+}
+
+/// Emitted when the supply of a stablecoin is updated through the treasury
+#[contractevent(topics = ["DaoUtils", "update_supply"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpdateSupply {
+    #[topic]
+    pub token: Address,
+    pub version: u32,
+    pub treasury: Address,
+    pub amount: i128,
+}
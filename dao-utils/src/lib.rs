@@ -1,6 +1,7 @@
 #![no_std]
 
 mod contract;
+pub mod events;
 pub mod dependencies;
 
 pub use contract::*;
@@ -0,0 +1,76 @@
+use bridge_oracle::BridgeOracleError;
+use soroban_sdk::xdr::ScErrorType;
+use soroban_sdk::{Error, InvokeError};
+use treasury::TreasuryError;
+
+/// An error returned by one of the Orbit contracts
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OrbitError {
+    Treasury(TreasuryError),
+    BridgeOracle(BridgeOracleError),
+}
+
+impl OrbitError {
+    /// Decode an Orbit contract error from its code
+    pub fn from_code(code: u32) -> Option<Self> {
+        let error = Error::from_contract_error(code);
+        TreasuryError::try_from(error)
+            .ok()
+            .map(OrbitError::Treasury)
+            .or_else(|| {
+                BridgeOracleError::try_from(error)
+                    .ok()
+                    .map(OrbitError::BridgeOracle)
+            })
+    }
+
+    /// Decode an Orbit contract error from an error string, such as `Error(Contract, #1504)`
+    /// as reported by a failed simulation or transaction
+    pub fn parse(message: &str) -> Option<Self> {
+        let start = message.find("Error(Contract, #")? + "Error(Contract, #".len();
+        let end = start + message[start..].find(')')?;
+        Self::from_code(message[start..end].parse().ok()?)
+    }
+
+    /// The contract error code
+    pub fn code(&self) -> u32 {
+        match self {
+            OrbitError::Treasury(error) => *error as u32,
+            OrbitError::BridgeOracle(error) => *error as u32,
+        }
+    }
+}
+
+impl TryFrom<Error> for OrbitError {
+    type Error = Error;
+
+    fn try_from(error: Error) -> Result<Self, Self::Error> {
+        if !error.is_type(ScErrorType::Contract) {
+            return Err(error);
+        }
+        Self::from_code(error.get_code()).ok_or(error)
+    }
+}
+
+impl TryFrom<InvokeError> for OrbitError {
+    type Error = InvokeError;
+
+    fn try_from(error: InvokeError) -> Result<Self, Self::Error> {
+        match error {
+            InvokeError::Contract(code) => Self::from_code(code).ok_or(error),
+            InvokeError::Abort => Err(error),
+        }
+    }
+}
+
+impl From<TreasuryError> for OrbitError {
+    fn from(error: TreasuryError) -> Self {
+        OrbitError::Treasury(error)
+    }
+}
+
+impl From<BridgeOracleError> for OrbitError {
+    fn from(error: BridgeOracleError) -> Self {
+        OrbitError::BridgeOracle(error)
+    }
+}
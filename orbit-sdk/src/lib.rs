@@ -0,0 +1,35 @@
+//! Typed clients, argument builders, error decoding and offline invocation XDR for the Orbit
+//! contracts.
+//!
+//! The clients and argument builders are the ones generated for the contract crates, so they
+//! always match the contracts in this workspace. This crate is meant for off-chain consumers
+//! and tests, and must not be linked into a contract.
+
+pub mod errors;
+pub mod xdr;
+
+pub use errors::OrbitError;
+pub use sep_40_oracle::{Asset, PriceData};
+
+pub mod treasury {
+    pub use treasury::events;
+    pub use treasury::{
+        ReserveReport, TreasuryClient, TreasuryContractArgs as TreasuryArgs, TreasuryError,
+    };
+}
+
+pub mod bridge_oracle {
+    pub use bridge_oracle::events;
+    pub use bridge_oracle::{
+        Adapter, AggregatePrice, Breaker, BridgeOracleClient,
+        BridgeOracleContractArgs as BridgeOracleArgs, BridgeOracleError, CrossLeg, DetailedPrice,
+        FallbackOracle, FixedPrice, Mapping, Observation, PriceBounds, PriceOverride, PriceSource,
+        PriceSources, PriceStatus, Rate, Ratio, TwapConfig, TwapState,
+    };
+}
+
+pub mod dao_utils {
+    pub use dao_utils::dependencies::bridge_oracle::Asset;
+    pub use dao_utils::events;
+    pub use dao_utils::{DaoUtilsClient, DaoUtilsContractArgs as DaoUtilsArgs};
+}
@@ -0,0 +1,806 @@
+//! Offline construction of Orbit contract invocations
+//!
+//! Invocations are built from strkeys and plain values, without an environment or a network,
+//! and can be encoded as a base64 `Operation` for a transaction builder to sign and submit.
+
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    AccountId, ContractId, Hash, HostFunction, Int128Parts, InvokeContractArgs,
+    InvokeHostFunctionOp, Limits, Operation, OperationBody, PublicKey, ScAddress, ScMap,
+    ScMapEntry, ScSymbol, ScVal, ScVec, Uint256, VecM, WriteXdr,
+};
+
+/// An error building an invocation
+#[derive(Debug)]
+pub enum XdrError {
+    /// The strkey is not a contract or account address
+    InvalidAddress,
+    /// The symbol is too long or contains invalid characters
+    InvalidSymbol,
+    Xdr(stellar_xdr::curr::Error),
+}
+
+impl From<stellar_xdr::curr::Error> for XdrError {
+    fn from(error: stellar_xdr::curr::Error) -> Self {
+        XdrError::Xdr(error)
+    }
+}
+
+/// A SEP-40 asset argument
+#[derive(Clone, Copy, Debug)]
+pub enum AssetArg<'a> {
+    /// A Stellar asset by its contract strkey
+    Stellar(&'a str),
+    /// An off-chain asset by its symbol
+    Other(&'a str),
+}
+
+/// A fallback oracle argument
+#[derive(Clone, Copy, Debug)]
+pub struct FallbackArg<'a> {
+    /// The oracle by its contract strkey
+    pub oracle: &'a str,
+    /// The max age of a price from the oracle in seconds, or 0 for no max age
+    pub max_age: u64,
+}
+
+/// A cross rate leg argument
+#[derive(Clone, Copy, Debug)]
+pub struct CrossLegArg<'a> {
+    /// The asset whose price is part of the cross rate
+    pub asset: AssetArg<'a>,
+    /// If the cross rate is divided by the price of the asset, instead of multiplied
+    pub inverse: bool,
+}
+
+/// A ratio rate argument
+#[derive(Clone, Copy, Debug)]
+pub enum RateArg<'a> {
+    /// A fixed rate
+    Fixed(i128),
+    /// The rate returned by a function of a contract by its strkey
+    Contract(&'a str, &'a str),
+    /// The b_rate of a Blend pool reserve, by the strkeys of the pool and the reserve asset
+    BlendBRate(&'a str, &'a str),
+}
+
+/// An oracle adapter argument
+#[derive(Clone, Copy, Debug)]
+pub enum AdapterArg {
+    /// The SEP-40 oracle interface
+    Sep40,
+    /// The Reflector oracle interface
+    Reflector,
+}
+
+/// Encode a contract or account strkey as an address value
+pub fn address_val(strkey: &str) -> Result<ScVal, XdrError> {
+    let address = match Strkey::from_string(strkey).map_err(|_| XdrError::InvalidAddress)? {
+        Strkey::Contract(contract) => ScAddress::Contract(ContractId(Hash(contract.0))),
+        Strkey::PublicKeyEd25519(key) => {
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))))
+        }
+        _ => return Err(XdrError::InvalidAddress),
+    };
+    Ok(ScVal::Address(address))
+}
+
+/// Encode a vector of contract or account strkeys as an address vector value
+pub fn address_vec_val(strkeys: &[&str]) -> Result<ScVal, XdrError> {
+    let addresses = strkeys
+        .iter()
+        .map(|strkey| address_val(strkey))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ScVal::Vec(Some(ScVec(addresses.try_into()?))))
+}
+
+/// Encode a symbol value
+pub fn symbol_val(symbol: &str) -> Result<ScVal, XdrError> {
+    Ok(ScVal::Symbol(ScSymbol(
+        symbol.try_into().map_err(|_| XdrError::InvalidSymbol)?,
+    )))
+}
+
+/// Encode an i128 value
+pub fn i128_val(value: i128) -> ScVal {
+    ScVal::I128(Int128Parts {
+        hi: (value >> 64) as i64,
+        lo: value as u64,
+    })
+}
+
+/// Encode a u32 value
+pub fn u32_val(value: u32) -> ScVal {
+    ScVal::U32(value)
+}
+
+/// Encode a u64 value
+pub fn u64_val(value: u64) -> ScVal {
+    ScVal::U64(value)
+}
+
+/// Encode a bool value
+pub fn bool_val(value: bool) -> ScVal {
+    ScVal::Bool(value)
+}
+
+/// Encode a SEP-40 asset value
+pub fn asset_val(asset: AssetArg) -> Result<ScVal, XdrError> {
+    let fields = match asset {
+        AssetArg::Stellar(strkey) => [symbol_val("Stellar")?, address_val(strkey)?],
+        AssetArg::Other(symbol) => [symbol_val("Other")?, symbol_val(symbol)?],
+    };
+    Ok(ScVal::Vec(Some(ScVec(fields.to_vec().try_into()?))))
+}
+
+/// Encode a vector of SEP-40 assets
+pub fn asset_vec_val(assets: &[AssetArg]) -> Result<ScVal, XdrError> {
+    let values = assets
+        .iter()
+        .map(|asset| asset_val(*asset))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ScVal::Vec(Some(ScVec(values.try_into()?))))
+}
+
+/// Encode a ratio rate value
+pub fn rate_val(rate: RateArg) -> Result<ScVal, XdrError> {
+    let fields = match rate {
+        RateArg::Fixed(rate) => vec![symbol_val("Fixed")?, i128_val(rate)],
+        RateArg::Contract(strkey, function) => {
+            vec![
+                symbol_val("Contract")?,
+                address_val(strkey)?,
+                symbol_val(function)?,
+            ]
+        }
+        RateArg::BlendBRate(pool, asset) => vec![
+            symbol_val("BlendBRate")?,
+            address_val(pool)?,
+            address_val(asset)?,
+        ],
+    };
+    Ok(ScVal::Vec(Some(ScVec(fields.try_into()?))))
+}
+
+/// Encode an oracle adapter value
+pub fn adapter_val(adapter: AdapterArg) -> Result<ScVal, XdrError> {
+    let variant = match adapter {
+        AdapterArg::Sep40 => "Sep40",
+        AdapterArg::Reflector => "Reflector",
+    };
+    Ok(ScVal::Vec(Some(ScVec(
+        vec![symbol_val(variant)?].try_into()?,
+    ))))
+}
+
+/// Encode a vector of fallback oracles
+pub fn fallback_vec_val(fallbacks: &[FallbackArg]) -> Result<ScVal, XdrError> {
+    let mut values = Vec::with_capacity(fallbacks.len());
+    for fallback in fallbacks {
+        // struct fields are encoded as a map sorted by field name
+        let entries = vec![
+            ScMapEntry {
+                key: symbol_val("max_age")?,
+                val: u64_val(fallback.max_age),
+            },
+            ScMapEntry {
+                key: symbol_val("oracle")?,
+                val: address_val(fallback.oracle)?,
+            },
+        ];
+        values.push(ScVal::Map(Some(ScMap(entries.try_into()?))));
+    }
+    Ok(ScVal::Vec(Some(ScVec(values.try_into()?))))
+}
+
+/// Encode a vector of cross rate legs
+pub fn cross_leg_vec_val(legs: &[CrossLegArg]) -> Result<ScVal, XdrError> {
+    let mut values = Vec::with_capacity(legs.len());
+    for leg in legs {
+        let entries = vec![
+            ScMapEntry {
+                key: symbol_val("asset")?,
+                val: asset_val(leg.asset)?,
+            },
+            ScMapEntry {
+                key: symbol_val("inverse")?,
+                val: ScVal::Bool(leg.inverse),
+            },
+        ];
+        values.push(ScVal::Map(Some(ScMap(entries.try_into()?))));
+    }
+    Ok(ScVal::Vec(Some(ScVec(values.try_into()?))))
+}
+
+/// A contract invocation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invocation {
+    pub args: InvokeContractArgs,
+}
+
+impl Invocation {
+    /// Create an invocation of `function` on the contract `contract_id` with `args`
+    pub fn new(contract_id: &str, function: &str, args: Vec<ScVal>) -> Result<Self, XdrError> {
+        let contract_address = match address_val(contract_id)? {
+            ScVal::Address(address @ ScAddress::Contract(_)) => address,
+            _ => return Err(XdrError::InvalidAddress),
+        };
+        Ok(Invocation {
+            args: InvokeContractArgs {
+                contract_address,
+                function_name: ScSymbol(function.try_into().map_err(|_| XdrError::InvalidSymbol)?),
+                args: args.try_into()?,
+            },
+        })
+    }
+
+    /// The host function invoking the contract
+    pub fn host_function(&self) -> HostFunction {
+        HostFunction::InvokeContract(self.args.clone())
+    }
+
+    /// The operation invoking the contract, without authorization entries
+    pub fn operation(&self) -> Operation {
+        Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: self.host_function(),
+                auth: VecM::default(),
+            }),
+        }
+    }
+
+    /// Encode the operation invoking the contract as base64 XDR
+    pub fn to_xdr_base64(&self) -> Result<String, XdrError> {
+        Ok(self.operation().to_xdr_base64(Limits::none())?)
+    }
+}
+
+/// Invocations of the treasury
+pub mod treasury {
+    use super::*;
+
+    pub fn add_stablecoin(
+        treasury: &str,
+        token: &str,
+        blend_pool: &str,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "add_stablecoin",
+            vec![address_val(token)?, address_val(blend_pool)?],
+        )
+    }
+
+    pub fn increase_supply(
+        treasury: &str,
+        token: &str,
+        amount: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "increase_supply",
+            vec![address_val(token)?, i128_val(amount)],
+        )
+    }
+
+    pub fn decrease_supply(
+        treasury: &str,
+        token: &str,
+        amount: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "decrease_supply",
+            vec![address_val(token)?, i128_val(amount)],
+        )
+    }
+
+    pub fn claim(treasury: &str, reserve_address: &str, to: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "claim",
+            vec![address_val(reserve_address)?, address_val(to)?],
+        )
+    }
+
+    pub fn set_oracle(treasury: &str, oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "set_oracle", vec![address_val(oracle)?])
+    }
+
+    pub fn set_peg_band(treasury: &str, token: &str, band: i128) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "set_peg_band",
+            vec![address_val(token)?, i128_val(band)],
+        )
+    }
+
+    pub fn set_backstop(treasury: &str, backstop: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "set_backstop", vec![address_val(backstop)?])
+    }
+
+    pub fn set_backstop_multiple(
+        treasury: &str,
+        token: &str,
+        multiple: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            treasury,
+            "set_backstop_multiple",
+            vec![address_val(token)?, i128_val(multiple)],
+        )
+    }
+
+    pub fn backstop_headroom(treasury: &str, token: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "backstop_headroom", vec![address_val(token)?])
+    }
+
+    pub fn reserve_report(treasury: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "reserve_report", vec![])
+    }
+
+    pub fn list_stablecoins(treasury: &str, tokens: &[&str]) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "list_stablecoins", vec![address_vec_val(tokens)?])
+    }
+
+    pub fn set_admin(treasury: &str, new_admin: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(treasury, "set_admin", vec![address_val(new_admin)?])
+    }
+}
+
+/// Invocations of the bridge oracle
+pub mod bridge_oracle {
+    use super::*;
+
+    pub fn add_asset(oracle: &str, asset: AssetArg, to: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "add_asset", vec![asset_val(asset)?, asset_val(to)?])
+    }
+
+    pub fn add_inverse_asset(
+        oracle: &str,
+        asset: AssetArg,
+        to: AssetArg,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "add_inverse_asset",
+            vec![asset_val(asset)?, asset_val(to)?],
+        )
+    }
+
+    pub fn remove_asset(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_asset", vec![asset_val(asset)?])
+    }
+
+    pub fn mappings(oracle: &str, start: u32, limit: u32) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "mappings", vec![u32_val(start), u32_val(limit)])
+    }
+
+    pub fn set_ratio(
+        oracle: &str,
+        asset: AssetArg,
+        rate: RateArg,
+        decimals: u32,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_ratio",
+            vec![asset_val(asset)?, rate_val(rate)?, u32_val(decimals)],
+        )
+    }
+
+    pub fn remove_ratio(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_ratio", vec![asset_val(asset)?])
+    }
+
+    pub fn ratio(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "ratio", vec![asset_val(asset)?])
+    }
+
+    pub fn set_comet_pool(oracle: &str, pool: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_comet_pool", vec![address_val(pool)?])
+    }
+
+    pub fn remove_comet_pool(oracle: &str, pool: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_comet_pool", vec![address_val(pool)?])
+    }
+
+    pub fn is_comet_pool(oracle: &str, pool: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "is_comet_pool", vec![address_val(pool)?])
+    }
+
+    pub fn set_cache(oracle: &str, enabled: bool) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_cache", vec![bool_val(enabled)])
+    }
+
+    pub fn cache(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "cache", vec![])
+    }
+
+    pub fn set_adapter(
+        oracle: &str,
+        adapted: &str,
+        adapter: AdapterArg,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_adapter",
+            vec![address_val(adapted)?, adapter_val(adapter)?],
+        )
+    }
+
+    pub fn adapter(oracle: &str, adapted: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "adapter", vec![address_val(adapted)?])
+    }
+
+    pub fn set_history_size(oracle: &str, size: u32) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_history_size", vec![u32_val(size)])
+    }
+
+    pub fn history_size(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "history_size", vec![])
+    }
+
+    pub fn history_twap(
+        oracle: &str,
+        asset: AssetArg,
+        records: u32,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "history_twap",
+            vec![asset_val(asset)?, u32_val(records)],
+        )
+    }
+
+    pub fn poke(oracle: &str, assets: &[AssetArg]) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "poke", vec![asset_vec_val(assets)?])
+    }
+
+    pub fn base(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "base", vec![])
+    }
+
+    pub fn assets(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "assets", vec![])
+    }
+
+    pub fn decimals(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "decimals", vec![])
+    }
+
+    pub fn resolution(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "resolution", vec![])
+    }
+
+    pub fn price(oracle: &str, asset: AssetArg, timestamp: u64) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "price", vec![asset_val(asset)?, u64_val(timestamp)])
+    }
+
+    pub fn prices(oracle: &str, asset: AssetArg, records: u32) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "prices", vec![asset_val(asset)?, u32_val(records)])
+    }
+
+    pub fn lastprice(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "lastprice", vec![asset_val(asset)?])
+    }
+
+    pub fn lastprice_strict(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "lastprice_strict", vec![asset_val(asset)?])
+    }
+
+    pub fn lastprice_detailed(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "lastprice_detailed", vec![asset_val(asset)?])
+    }
+
+    pub fn set_max_age(
+        oracle: &str,
+        asset: AssetArg,
+        max_age: u64,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_max_age",
+            vec![asset_val(asset)?, u64_val(max_age)],
+        )
+    }
+
+    pub fn max_age(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "max_age", vec![asset_val(asset)?])
+    }
+
+    pub fn set_price_sources(
+        oracle: &str,
+        asset: AssetArg,
+        sources: &[&str],
+        quorum: u32,
+        tolerance: i128,
+        max_age: u64,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_price_sources",
+            vec![
+                asset_val(asset)?,
+                address_vec_val(sources)?,
+                u32_val(quorum),
+                i128_val(tolerance),
+                u64_val(max_age),
+            ],
+        )
+    }
+
+    pub fn price_sources(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "price_sources", vec![asset_val(asset)?])
+    }
+
+    pub fn lastprice_sources(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "lastprice_sources", vec![asset_val(asset)?])
+    }
+
+    pub fn set_fallbacks(
+        oracle: &str,
+        asset: AssetArg,
+        fallbacks: &[FallbackArg],
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_fallbacks",
+            vec![asset_val(asset)?, fallback_vec_val(fallbacks)?],
+        )
+    }
+
+    pub fn fallbacks(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "fallbacks", vec![asset_val(asset)?])
+    }
+
+    pub fn set_cross_rate(
+        oracle: &str,
+        asset: AssetArg,
+        legs: &[CrossLegArg],
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_cross_rate",
+            vec![asset_val(asset)?, cross_leg_vec_val(legs)?],
+        )
+    }
+
+    pub fn cross_rate(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "cross_rate", vec![asset_val(asset)?])
+    }
+
+    pub fn set_fixed_price(
+        oracle: &str,
+        asset: AssetArg,
+        price: i128,
+        decimals: u32,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_fixed_price",
+            vec![asset_val(asset)?, i128_val(price), u32_val(decimals)],
+        )
+    }
+
+    pub fn remove_fixed_price(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_fixed_price", vec![asset_val(asset)?])
+    }
+
+    pub fn fixed_price(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "fixed_price", vec![asset_val(asset)?])
+    }
+
+    pub fn set_bounds(
+        oracle: &str,
+        asset: AssetArg,
+        min: i128,
+        max: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_bounds",
+            vec![asset_val(asset)?, i128_val(min), i128_val(max)],
+        )
+    }
+
+    pub fn remove_bounds(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_bounds", vec![asset_val(asset)?])
+    }
+
+    pub fn bounds(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "bounds", vec![asset_val(asset)?])
+    }
+
+    pub fn set_guardian(oracle: &str, guardian: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_guardian", vec![address_val(guardian)?])
+    }
+
+    pub fn guardian(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "guardian", vec![])
+    }
+
+    pub fn set_breaker(
+        oracle: &str,
+        asset: AssetArg,
+        max_deviation: i128,
+        window: u64,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_breaker",
+            vec![asset_val(asset)?, i128_val(max_deviation), u64_val(window)],
+        )
+    }
+
+    pub fn breaker(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "breaker", vec![asset_val(asset)?])
+    }
+
+    pub fn last_good_price(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "last_good_price", vec![asset_val(asset)?])
+    }
+
+    pub fn freeze(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "freeze", vec![asset_val(asset)?])
+    }
+
+    pub fn unfreeze(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "unfreeze", vec![asset_val(asset)?])
+    }
+
+    pub fn frozen(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "frozen", vec![asset_val(asset)?])
+    }
+
+    pub fn set_override_band(oracle: &str, band: i128) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_override_band", vec![i128_val(band)])
+    }
+
+    pub fn override_band(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "override_band", vec![])
+    }
+
+    pub fn set_override(
+        oracle: &str,
+        asset: AssetArg,
+        price: i128,
+        expires_at: u64,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_override",
+            vec![asset_val(asset)?, i128_val(price), u64_val(expires_at)],
+        )
+    }
+
+    pub fn remove_override(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_override", vec![asset_val(asset)?])
+    }
+
+    pub fn price_override(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "price_override", vec![asset_val(asset)?])
+    }
+
+    pub fn set_twap(
+        oracle: &str,
+        asset: AssetArg,
+        pair: &str,
+        window: u64,
+        min_liquidity: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_twap",
+            vec![
+                asset_val(asset)?,
+                address_val(pair)?,
+                u64_val(window),
+                i128_val(min_liquidity),
+            ],
+        )
+    }
+
+    pub fn remove_twap(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_twap", vec![asset_val(asset)?])
+    }
+
+    pub fn twap(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "twap", vec![asset_val(asset)?])
+    }
+
+    pub fn observe(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "observe", vec![asset_val(asset)?])
+    }
+
+    pub fn set_route(oracle: &str, asset: AssetArg, route: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_route",
+            vec![asset_val(asset)?, address_val(route)?],
+        )
+    }
+
+    pub fn remove_route(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "remove_route", vec![asset_val(asset)?])
+    }
+
+    pub fn route(oracle: &str, asset: AssetArg) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "route", vec![asset_val(asset)?])
+    }
+
+    pub fn set_oracles(
+        oracle: &str,
+        stellar_oracle: &str,
+        other_oracle: &str,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            oracle,
+            "set_oracles",
+            vec![address_val(stellar_oracle)?, address_val(other_oracle)?],
+        )
+    }
+
+    pub fn stellar_oracle(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "stellar_oracle", vec![])
+    }
+
+    pub fn other_oracle(oracle: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "other_oracle", vec![])
+    }
+
+    pub fn set_admin(oracle: &str, new_admin: &str) -> Result<Invocation, XdrError> {
+        Invocation::new(oracle, "set_admin", vec![address_val(new_admin)?])
+    }
+}
+
+/// Invocations of the dao utils
+pub mod dao_utils {
+    use super::*;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_stablecoin(
+        dao_utils: &str,
+        admin: &str,
+        treasury: &str,
+        oracle: &str,
+        token: &str,
+        asset: AssetArg,
+        blend_pool: &str,
+        initial_supply: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            dao_utils,
+            "new_stablecoin",
+            vec![
+                address_val(admin)?,
+                address_val(treasury)?,
+                address_val(oracle)?,
+                address_val(token)?,
+                asset_val(asset)?,
+                address_val(blend_pool)?,
+                i128_val(initial_supply),
+            ],
+        )
+    }
+
+    pub fn update_supply(
+        dao_utils: &str,
+        admin: &str,
+        treasury: &str,
+        token: &str,
+        amount: i128,
+    ) -> Result<Invocation, XdrError> {
+        Invocation::new(
+            dao_utils,
+            "update_supply",
+            vec![
+                address_val(admin)?,
+                address_val(treasury)?,
+                address_val(token)?,
+                i128_val(amount),
+            ],
+        )
+    }
+}
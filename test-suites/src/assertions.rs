@@ -1,8 +1,11 @@
 use std::fmt::Debug;
 use std::ops::{Add, Sub};
 
-use crate::test_fixture::SCALAR_7;
+use crate::test_fixture::{SCALAR_12, SCALAR_7};
 use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::testutils::Events as _;
+use soroban_sdk::{vec as svec, Address, Env, Event};
+use treasury::ReserveReport;
 
 pub fn assert_approx_eq_abs<T>(a: T, b: T, delta: T)
 where
@@ -24,4 +27,37 @@ where
 pub fn assert_approx_eq_rel(a: i128, b: i128, delta: i128) {
     let abs_delta = b.fixed_mul_floor(delta, SCALAR_7).unwrap();
     assert_approx_eq_abs(a, b, abs_delta);
-}
\ No newline at end of file
+}
+
+/// Assert that a treasury reserve report is internally consistent, such that the supply tracked
+/// by the treasury is backed by b-tokens or reported as a deficit.
+pub fn assert_reserve_report(report: &ReserveReport) {
+    assert_eq!(
+        report.underlying,
+        report.b_tokens.fixed_mul_floor(report.b_rate, SCALAR_12).unwrap(),
+        "underlying does not match the b-tokens at the b_rate"
+    );
+    assert_eq!(
+        report.interest - report.deficit,
+        report.underlying - report.total_supply,
+        "interest and deficit do not match the underlying"
+    );
+    assert!(report.interest >= 0 && report.deficit >= 0);
+    assert!(report.interest == 0 || report.deficit == 0);
+}
+
+/// Assert that `contract` published exactly the `expected` events, in order, during the last
+/// invocation.
+pub fn assert_events(env: &Env, contract: &Address, expected: &[&dyn Event]) {
+    let mut published = svec![env];
+    for event in env.events().all().iter() {
+        if event.0 == *contract {
+            published.push_back(event);
+        }
+    }
+    let mut events = svec![env];
+    for event in expected {
+        events.push_back((contract.clone(), event.topics(env), event.data(env)));
+    }
+    assert_eq!(published, events);
+}
@@ -0,0 +1,119 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Symbol};
+use test_suites::assertions::assert_approx_eq_rel;
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TestFixture, TokenIndex, SCALAR_7};
+
+/// Create a fixture capping OUSD to `multiple` times its backstop, with BLND at 0.1 USD and the
+/// shares of the BLND/USDC Comet pool priced at fair value by the bridge oracle
+fn create_fixture_with_cap<'a>(multiple: i128) -> TestFixture<'a> {
+    let fixture = create_fixture_with_data(false);
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    fixture.bridge_oracle.set_fixed_price(&blnd, &0_1000000, &7);
+    fixture.bridge_oracle.set_comet_pool(&fixture.lp.address);
+    fixture.treasury.set_oracle(&fixture.bridge_oracle.address);
+    fixture.treasury.set_backstop(&fixture.backstop.address);
+    fixture.treasury.set_backstop_multiple(&fixture.tokens[TokenIndex::OUSD].address, &multiple);
+    fixture
+}
+
+#[test]
+fn test_backstop_headroom() {
+    let fixture = create_fixture_with_cap(100_0000000);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    // the LP is balanced at the oracle prices, so the USDC side of the 80/20 pool is 20% of
+    // the backstop value
+    let pool_data = fixture.backstop.pool_data(&fixture.pools[0].pool.address);
+    let expected_cap = pool_data.usdc * 5 * 100;
+    let headroom = fixture.treasury.backstop_headroom(&ousd.address).unwrap();
+    assert_approx_eq_rel(headroom, expected_cap - 1_000_000 * SCALAR_7, 0_0000100);
+
+    fixture.treasury.increase_supply(&ousd.address, &(100_000 * SCALAR_7));
+    assert_eq!(
+        headroom - 100_000 * SCALAR_7,
+        fixture.treasury.backstop_headroom(&ousd.address).unwrap()
+    );
+}
+
+#[test]
+fn test_backstop_headroom_non_usd() {
+    let fixture = create_fixture_with_cap(100_0000000);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let usd_headroom = fixture.treasury.backstop_headroom(&ousd.address).unwrap();
+
+    // priced as EURO at 1.1 USD, the same backstop value caps fewer tokens
+    fixture.bridge_oracle.add_asset(
+        &Asset::Stellar(ousd.address.clone()),
+        &Asset::Other(Symbol::new(&fixture.env, "EURO")),
+    );
+    let headroom = fixture.treasury.backstop_headroom(&ousd.address).unwrap();
+    let usd_cap = usd_headroom + 1_000_000 * SCALAR_7;
+    assert_approx_eq_rel(headroom, usd_cap * 10 / 11 - 1_000_000 * SCALAR_7, 0_0000100);
+}
+
+#[test]
+fn test_backstop_headroom_resists_manipulation() {
+    let fixture = create_fixture_with_cap(100_0000000);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let headroom = fixture.treasury.backstop_headroom(&ousd.address).unwrap();
+
+    // a large swap into the LP moves its USDC balance, but not the value of the backstop
+    let trader = Address::generate(&fixture.env);
+    let amount_in = fixture.lp.get_balance(&usdc.address) / 4;
+    usdc.mint(&trader, &amount_in);
+    fixture.lp.swap_exact_amount_in(&usdc.address, &amount_in, &blnd.address, &0, &i128::MAX, &trader);
+
+    assert_approx_eq_rel(fixture.treasury.backstop_headroom(&ousd.address).unwrap(), headroom, 0_0010000);
+}
+
+#[test]
+fn test_backstop_headroom_not_capped() {
+    let fixture = create_fixture_with_data(false);
+
+    assert_eq!(None, fixture.treasury.backstop_headroom(&fixture.tokens[TokenIndex::OUSD].address));
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1513)"]
+fn test_increase_supply_above_cap() {
+    let fixture = create_fixture_with_cap(100_0000000);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let headroom = fixture.treasury.backstop_headroom(&ousd.address).unwrap();
+    fixture.treasury.increase_supply(&ousd.address, &(headroom + 1));
+}
+
+#[test]
+fn test_decrease_supply_above_cap() {
+    let fixture = create_fixture_with_cap(1_0000000);
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    assert_eq!(0, fixture.treasury.backstop_headroom(&ousd.address).unwrap());
+
+    let pool_balance = ousd.balance(&fixture.pools[0].pool.address);
+    fixture.treasury.decrease_supply(&ousd.address, &(100_000 * SCALAR_7));
+    assert_eq!(
+        pool_balance - 100_000 * SCALAR_7,
+        ousd.balance(&fixture.pools[0].pool.address)
+    );
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1514)"]
+fn test_backstop_multiple_without_backstop() {
+    let fixture = create_fixture_with_data(false);
+
+    fixture.treasury.set_backstop_multiple(&fixture.tokens[TokenIndex::OUSD].address, &10_0000000);
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1515)"]
+fn test_backstop_multiple_without_oracle() {
+    let fixture = create_fixture_with_data(false);
+    fixture.treasury.set_backstop(&fixture.backstop.address);
+
+    fixture.treasury.set_backstop_multiple(&fixture.tokens[TokenIndex::OUSD].address, &10_0000000);
+}
@@ -0,0 +1,133 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec as svec, Address};
+use test_suites::create_fixture_with_data;
+use test_suites::dependencies::pool::{Request, RequestType};
+use test_suites::test_fixture::{TestFixture, TokenIndex, SCALAR_7};
+
+/// The CPU instructions spent by `f`
+fn measure_cpu(fixture: &TestFixture, f: impl FnOnce()) -> u64 {
+    fixture.env.cost_estimate().budget().reset_tracker();
+    f();
+    fixture.env.cost_estimate().budget().cpu_instruction_cost()
+}
+
+/// The CPU instructions spent by the second of two `lastprice` calls for `asset` in a ledger
+fn measure_repeated_lastprice(fixture: &TestFixture, asset: &Asset) -> u64 {
+    let first = fixture.bridge_oracle.lastprice(asset).unwrap();
+    measure_cpu(fixture, || {
+        let second = fixture.bridge_oracle.lastprice(asset).unwrap();
+        assert_eq!(first.price, second.price);
+    })
+}
+
+/// Check the cost of reading the price of `asset` with the cache enabled, before and after it
+/// is poked in the ledger, against the cost of reading it without the cache
+fn assert_cached_lastprice(fixture: &TestFixture, asset: &Asset, uncached: u64, ratio: u64) {
+    fixture.bridge_oracle.set_cache(&true);
+
+    // a read before the asset is poked resolves the price without writing to the ledger
+    let cold = measure_cpu(fixture, || {
+        fixture.bridge_oracle.lastprice(asset).unwrap();
+    });
+    assert!(cold < uncached * 11 / 10);
+    let resources = fixture.env.cost_estimate().resources();
+    assert_eq!((resources.write_entries, resources.write_bytes), (0, 0));
+    assert_eq!(resources.temporary_rent_ledger_bytes, 0);
+    let cold_reads = resources.memory_read_entries;
+
+    // the poke keeps the price in a single temporary entry, next to the last accepted price
+    fixture.bridge_oracle.poke(&svec![&fixture.env, asset.clone()]);
+    let resources = fixture.env.cost_estimate().resources();
+    assert_eq!(resources.write_entries, 2);
+    assert_eq!((resources.temporary_entry_rent_bumps, resources.persistent_entry_rent_bumps), (1, 1));
+
+    let cached = measure_cpu(fixture, || {
+        fixture.bridge_oracle.lastprice(asset).unwrap();
+    });
+    assert!(cached < uncached / ratio);
+    let resources = fixture.env.cost_estimate().resources();
+    assert_eq!(resources.write_entries, 0);
+    assert!(resources.memory_read_entries < cold_reads / 2);
+}
+
+#[test]
+fn test_cache_lastprice_budget() {
+    let fixture = create_fixture_with_data(false);
+    let xlm = Asset::Stellar(fixture.tokens[TokenIndex::XLM].address.clone());
+
+    let uncached = measure_repeated_lastprice(&fixture, &xlm);
+    // a cached price is a single temporary storage read instead of an oracle call
+    assert_cached_lastprice(&fixture, &xlm, uncached, 5);
+}
+
+#[test]
+fn test_cache_comet_budget() {
+    let fixture = create_fixture_with_data(false);
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    let lp = Asset::Stellar(fixture.lp.address.clone());
+    fixture.bridge_oracle.set_fixed_price(&blnd, &0_1000000, &7);
+    fixture.bridge_oracle.set_comet_pool(&fixture.lp.address);
+
+    let uncached = measure_repeated_lastprice(&fixture, &lp);
+    // the fair value of a share prices every token of the pool and reads its balances
+    assert_cached_lastprice(&fixture, &lp, uncached, 20);
+}
+
+#[test]
+fn test_cache_submit_budget() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let henk = Address::generate(&fixture.env);
+    xlm.mint(&henk, &(1_000_000 * SCALAR_7));
+    let supply = svec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.address.clone(),
+            amount: 1_000_000 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&henk, &henk, &henk, &supply);
+    let borrow = svec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: ousd.address.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+
+    // each borrow runs a health check, which prices every reserve of the user
+    pool_fixture.pool.submit(&henk, &henk, &henk, &borrow);
+    let uncached = measure_cpu(&fixture, || {
+        pool_fixture.pool.submit(&henk, &henk, &henk, &borrow);
+    });
+    let resources = fixture.env.cost_estimate().resources();
+    let uncached_writes = (resources.write_entries, resources.temporary_entry_rent_bumps);
+
+    // before the reserves are poked, the cache adds no writes to the submit
+    fixture.bridge_oracle.set_cache(&true);
+    let cold = measure_cpu(&fixture, || {
+        pool_fixture.pool.submit(&henk, &henk, &henk, &borrow);
+    });
+    assert!(cold < uncached * 11 / 10);
+    let resources = fixture.env.cost_estimate().resources();
+    assert_eq!((resources.write_entries, resources.temporary_entry_rent_bumps), uncached_writes);
+
+    let reserves = svec![
+        &fixture.env,
+        Asset::Stellar(xlm.address.clone()),
+        Asset::Stellar(ousd.address.clone())
+    ];
+    fixture.bridge_oracle.poke(&reserves);
+    let cached = measure_cpu(&fixture, || {
+        pool_fixture.pool.submit(&henk, &henk, &henk, &borrow);
+    });
+    assert!(cached < uncached * 3 / 4);
+    let resources = fixture.env.cost_estimate().resources();
+    assert_eq!((resources.write_entries, resources.temporary_entry_rent_bumps), uncached_writes);
+}
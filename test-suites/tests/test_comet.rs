@@ -0,0 +1,115 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+use bridge_oracle::PriceSource;
+use test_suites::assertions::assert_approx_eq_rel;
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TestFixture, TokenIndex, SCALAR_7};
+
+/// Create a fixture pricing BLND at `blnd_price` USD with 7 decimals, and the shares of the
+/// BLND/USDC Comet pool at fair value
+fn create_fixture_with_comet<'a>(blnd_price: i128) -> TestFixture<'a> {
+    let fixture = create_fixture_with_data(false);
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    fixture
+        .bridge_oracle
+        .set_fixed_price(&blnd, &blnd_price, &7);
+    fixture.bridge_oracle.set_comet_pool(&fixture.lp.address);
+    fixture
+}
+
+/// The spot value of a share of the Comet pool, from its balances
+fn spot_share_price(fixture: &TestFixture, blnd_price: i128) -> i128 {
+    let blnd = fixture
+        .lp
+        .get_balance(&fixture.tokens[TokenIndex::BLND].address);
+    let usdc = fixture
+        .lp
+        .get_balance(&fixture.tokens[TokenIndex::USDC].address);
+    let value = blnd * blnd_price / SCALAR_7 + usdc;
+    value * SCALAR_7 / fixture.lp.get_total_supply() * 10_000_000
+}
+
+#[test]
+fn test_comet_fair_value() {
+    let fixture = create_fixture_with_comet(0_1000000);
+    let lp = Asset::Stellar(fixture.lp.address.clone());
+    assert!(fixture.bridge_oracle.is_comet_pool(&fixture.lp.address));
+    assert!(fixture.bridge_oracle.assets().contains(&lp));
+
+    // the pool is balanced at the oracle prices, so a share is worth its spot value of 1.25 USD
+    let price = fixture.bridge_oracle.lastprice(&lp).unwrap();
+    assert_approx_eq_rel(price.price, 1_25_000_000_000_000, 0_0000010);
+    assert_approx_eq_rel(
+        price.price,
+        spot_share_price(&fixture, 0_1000000),
+        0_0000010,
+    );
+    assert_eq!(
+        fixture.bridge_oracle.lastprice_sources(&lp),
+        soroban_sdk::vec![&fixture.env, fixture.oracle.address.clone()]
+    );
+    assert!(fixture.bridge_oracle.price(&lp, &price.timestamp).is_none());
+    let detailed = fixture.bridge_oracle.lastprice_detailed(&lp).unwrap();
+    assert!(detailed.source == PriceSource::Comet);
+    assert_eq!(detailed.count, 1);
+
+    fixture.bridge_oracle.remove_comet_pool(&fixture.lp.address);
+    assert!(!fixture.bridge_oracle.is_comet_pool(&fixture.lp.address));
+    assert!(!fixture.bridge_oracle.assets().contains(&lp));
+    assert!(fixture.bridge_oracle.lastprice(&lp).is_none());
+}
+
+#[test]
+fn test_comet_fair_value_off_balance() {
+    // BLND doubles, so the pool holds more BLND than it would after arbitrage
+    let fixture = create_fixture_with_comet(0_2000000);
+    let lp = Asset::Stellar(fixture.lp.address.clone());
+
+    // 1.25 * 2 ^ 0.8, below the spot value of the balances of 2.25
+    let price = fixture.bridge_oracle.lastprice(&lp).unwrap();
+    assert_approx_eq_rel(price.price, 2_17_637_640_824_031, 0_0000010);
+    assert_approx_eq_rel(
+        spot_share_price(&fixture, 0_2000000),
+        2_25_000_000_000_000,
+        0_0000010,
+    );
+}
+
+#[test]
+fn test_comet_fair_value_resists_manipulation() {
+    let fixture = create_fixture_with_comet(0_1000000);
+    let lp = Asset::Stellar(fixture.lp.address.clone());
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+    let usdc = &fixture.tokens[TokenIndex::USDC];
+    let fair_value = fixture.bridge_oracle.lastprice(&lp).unwrap().price;
+    let spot_value = spot_share_price(&fixture, 0_1000000);
+
+    // a large swap moves the spot value of a share, but not its fair value beyond the fees
+    let trader = Address::generate(&fixture.env);
+    let amount_in = fixture.lp.get_balance(&usdc.address) / 4;
+    usdc.mint(&trader, &amount_in);
+    fixture.lp.swap_exact_amount_in(
+        &usdc.address,
+        &amount_in,
+        &blnd.address,
+        &0,
+        &i128::MAX,
+        &trader,
+    );
+
+    assert!(spot_share_price(&fixture, 0_1000000) > spot_value * 1005 / 1000);
+    let price = fixture.bridge_oracle.lastprice(&lp).unwrap().price;
+    assert!(price >= fair_value);
+    assert_approx_eq_rel(price, fair_value, 0_0010000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1415)")]
+fn test_comet_not_a_pool() {
+    let fixture = create_fixture_with_data(false);
+
+    fixture
+        .bridge_oracle
+        .set_comet_pool(&fixture.tokens[TokenIndex::BLND].address);
+}
@@ -0,0 +1,108 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::{Address, IntoVal, Symbol};
+use test_suites::assertions::assert_events;
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TokenIndex, SCALAR_7};
+
+#[test]
+fn test_update_supply_events() {
+    let fixture = create_fixture_with_data(false);
+    let pool = &fixture.pools[0].pool.address;
+    let ousd = &fixture.tokens[TokenIndex::OUSD].address;
+    let treasury = &fixture.treasury.address;
+
+    fixture.dao_utils.update_supply(&fixture.admin, treasury, ousd, &(100 * SCALAR_7));
+    assert_events(
+        &fixture.env,
+        treasury,
+        &[&treasury::events::IncreaseSupply {
+            token: ousd.clone(),
+            pool: pool.clone(),
+            version: treasury::events::EVENT_VERSION,
+            amount: 100 * SCALAR_7,
+        }],
+    );
+    assert_events(
+        &fixture.env,
+        &fixture.dao_utils.address,
+        &[&dao_utils::events::UpdateSupply {
+            token: ousd.clone(),
+            version: dao_utils::events::EVENT_VERSION,
+            treasury: treasury.clone(),
+            amount: 100 * SCALAR_7,
+        }],
+    );
+
+    fixture.dao_utils.update_supply(&fixture.admin, treasury, ousd, &(-50 * SCALAR_7));
+    assert_events(
+        &fixture.env,
+        treasury,
+        &[&treasury::events::DecreaseSupply {
+            token: ousd.clone(),
+            pool: pool.clone(),
+            version: treasury::events::EVENT_VERSION,
+            amount: 50 * SCALAR_7,
+        }],
+    );
+}
+
+#[test]
+fn test_treasury_config_events() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD].address;
+    let treasury = &fixture.treasury.address;
+
+    fixture.treasury.set_peg_band(ousd, &0_0500000);
+    assert_events(
+        &fixture.env,
+        treasury,
+        &[&treasury::events::SetPegBand {
+            token: ousd.clone(),
+            version: treasury::events::EVENT_VERSION,
+            band: 0_0500000,
+        }],
+    );
+
+    let new_admin = Address::generate(&fixture.env);
+    fixture.treasury.set_admin(&new_admin);
+    assert_events(
+        &fixture.env,
+        treasury,
+        &[&treasury::events::SetAdmin {
+            version: treasury::events::EVENT_VERSION,
+            admin: new_admin,
+        }],
+    );
+}
+
+#[test]
+fn test_bridge_oracle_events() {
+    let fixture = create_fixture_with_data(false);
+    let bridge_oracle = &fixture.bridge_oracle.address;
+
+    let asset = Asset::Stellar(Address::generate(&fixture.env));
+    let to = Asset::Other(Symbol::new(&fixture.env, "EURO"));
+    fixture.bridge_oracle.add_asset(&asset, &to);
+    assert_events(
+        &fixture.env,
+        bridge_oracle,
+        &[&bridge_oracle::events::AddAsset {
+            version: bridge_oracle::events::EVENT_VERSION,
+            asset: asset.clone(),
+            to,
+        }],
+    );
+
+    // the asset is a topic, so indexers can filter events by asset
+    let event = fixture.env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        soroban_sdk::vec![
+            &fixture.env,
+            Symbol::new(&fixture.env, "BridgeOracle").into_val(&fixture.env),
+            Symbol::new(&fixture.env, "add_asset").into_val(&fixture.env),
+            asset.into_val(&fixture.env),
+        ]
+    );
+}
@@ -0,0 +1,103 @@
+use sep_40_oracle::testutils::Asset;
+use soroban_sdk::{vec as svec, Symbol};
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TestFixture, TokenIndex};
+
+/// Set the market price of OUSD on the stellar oracle, keeping the other fixture prices
+fn set_ousd_price(fixture: &TestFixture, price: i128) {
+    let e = &fixture.env;
+    fixture.oracle.set_data(
+        &fixture.admin,
+        &Asset::Other(Symbol::new(e, "USD")),
+        &svec![
+            e,
+            Asset::Stellar(fixture.tokens[TokenIndex::USDC].address.clone()),
+            Asset::Stellar(fixture.tokens[TokenIndex::XLM].address.clone()),
+            Asset::Other(Symbol::new(e, "USD")),
+            Asset::Other(Symbol::new(e, "EURO")),
+            Asset::Other(Symbol::new(e, "GBP")),
+            Asset::Stellar(fixture.tokens[TokenIndex::OUSD].address.clone()),
+        ],
+        &14,
+        &300,
+    );
+    fixture.oracle.set_price_stable(&svec![
+        e,
+        1_00_000_000_000_000,
+        0_10_000_000_000_000,
+        1_00_000_000_000_000,
+        1_10_000_000_000_000,
+        1_20_000_000_000_000,
+        price,
+    ]);
+}
+
+fn create_fixture_with_peg<'a>(price: i128) -> TestFixture<'a> {
+    let fixture = create_fixture_with_data(false);
+    set_ousd_price(&fixture, price);
+    fixture.treasury.set_oracle(&fixture.bridge_oracle.address);
+    fixture.treasury.set_peg_band(&fixture.tokens[TokenIndex::OUSD].address, &0_0500000);
+    fixture
+}
+
+#[test]
+fn test_increase_supply_on_peg() {
+    let fixture = create_fixture_with_peg(0_96_000_000_000_000);
+    let pool_fixture = &fixture.pools[0];
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let ousd_balance = ousd.balance(&pool_fixture.pool.address);
+    fixture.treasury.increase_supply(&ousd.address, &100_000);
+
+    assert_eq!(ousd_balance + 100_000, ousd.balance(&pool_fixture.pool.address));
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1510)"]
+fn test_increase_supply_below_peg() {
+    let fixture = create_fixture_with_peg(0_94_000_000_000_000);
+
+    fixture.treasury.increase_supply(&fixture.tokens[TokenIndex::OUSD].address, &100_000);
+}
+
+#[test]
+fn test_decrease_supply_on_peg() {
+    let fixture = create_fixture_with_peg(1_04_000_000_000_000);
+    let pool_fixture = &fixture.pools[0];
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    let ousd_balance = ousd.balance(&pool_fixture.pool.address);
+    fixture.treasury.decrease_supply(&ousd.address, &100_000);
+
+    assert_eq!(ousd_balance - 100_000, ousd.balance(&pool_fixture.pool.address));
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1510)"]
+fn test_decrease_supply_above_peg() {
+    let fixture = create_fixture_with_peg(1_06_000_000_000_000);
+
+    fixture.treasury.decrease_supply(&fixture.tokens[TokenIndex::OUSD].address, &100_000);
+}
+
+#[test]
+fn test_supply_changes_without_band() {
+    let fixture = create_fixture_with_data(false);
+    set_ousd_price(&fixture, 0_50_000_000_000_000);
+    fixture.treasury.set_oracle(&fixture.bridge_oracle.address);
+
+    let pool_fixture = &fixture.pools[0];
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+    let ousd_balance = ousd.balance(&pool_fixture.pool.address);
+    fixture.treasury.increase_supply(&ousd.address, &100_000);
+
+    assert_eq!(ousd_balance + 100_000, ousd.balance(&pool_fixture.pool.address));
+}
+
+#[test]
+#[should_panic = "Error(Contract, #1512)"]
+fn test_invalid_peg_band() {
+    let fixture = create_fixture_with_data(false);
+
+    fixture.treasury.set_peg_band(&fixture.tokens[TokenIndex::OUSD].address, &1_0000000);
+}
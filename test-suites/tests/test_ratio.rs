@@ -0,0 +1,67 @@
+use orbit_sdk::bridge_oracle::Rate;
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec as svec, Address, Symbol};
+use test_suites::create_fixture_with_data;
+use test_suites::dependencies::pool::{Request, RequestType};
+use test_suites::test_fixture::{TokenIndex, SCALAR_7};
+
+#[test]
+fn test_ratio_blend_b_rate() {
+    let fixture = create_fixture_with_data(false);
+    let pool = &fixture.pools[0].pool;
+    let ousd = &fixture.tokens[TokenIndex::OUSD];
+
+    // a b_token of the OUSD reserve, priced as USD scaled by the b_rate
+    let b_ousd = Asset::Other(Symbol::new(&fixture.env, "BOUSD"));
+    fixture.bridge_oracle.add_asset(&b_ousd, &Asset::Other(Symbol::new(&fixture.env, "USD")));
+    let rate = Rate::BlendBRate(pool.address.clone(), ousd.address.clone());
+    fixture.bridge_oracle.set_ratio(&b_ousd, &rate, &12);
+    let b_rate = pool.get_reserve(&ousd.address).data.b_rate;
+    let decimals = fixture.bridge_oracle.decimals();
+    let price = fixture.bridge_oracle.lastprice(&b_ousd).unwrap().price;
+    assert_eq!(price, 10i128.pow(decimals) * b_rate / 1_000_000_000_000);
+
+    // interest accrues to the b_token
+    let henk = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::XLM].mint(&henk, &(1_000_000 * SCALAR_7));
+    let requests = svec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 1_000_000 * SCALAR_7,
+        },
+        Request { request_type: RequestType::Borrow as u32, address: ousd.address.clone(), amount: 50_000 * SCALAR_7 },
+    ];
+    pool.submit(&henk, &henk, &henk, &requests);
+    fixture.jump(30 * 24 * 60 * 60);
+    pool.submit(&henk, &henk, &henk, &svec![&fixture.env]);
+
+    let accrued = pool.get_reserve(&ousd.address).data.b_rate;
+    assert!(accrued > b_rate);
+    let price = fixture.bridge_oracle.lastprice(&b_ousd).unwrap().price;
+    assert_eq!(price, 10i128.pow(decimals) * accrued / 1_000_000_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1414)")]
+fn test_ratio_blend_b_rate_decimals() {
+    let fixture = create_fixture_with_data(false);
+    let b_ousd = Asset::Other(Symbol::new(&fixture.env, "BOUSD"));
+    fixture.bridge_oracle.add_asset(&b_ousd, &Asset::Other(Symbol::new(&fixture.env, "USD")));
+
+    let rate = Rate::BlendBRate(fixture.pools[0].pool.address.clone(), fixture.tokens[TokenIndex::OUSD].address.clone());
+    fixture.bridge_oracle.set_ratio(&b_ousd, &rate, &7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1414)")]
+fn test_ratio_blend_b_rate_no_reserve() {
+    let fixture = create_fixture_with_data(false);
+    let b_blnd = Asset::Other(Symbol::new(&fixture.env, "BBLND"));
+    fixture.bridge_oracle.add_asset(&b_blnd, &Asset::Other(Symbol::new(&fixture.env, "USD")));
+
+    let rate = Rate::BlendBRate(fixture.pools[0].pool.address.clone(), fixture.tokens[TokenIndex::BLND].address.clone());
+    fixture.bridge_oracle.set_ratio(&b_blnd, &rate, &12);
+}
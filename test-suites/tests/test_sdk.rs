@@ -0,0 +1,176 @@
+use orbit_sdk::bridge_oracle::{Adapter, BridgeOracleArgs, BridgeOracleError, CrossLeg, FallbackOracle, Rate};
+use orbit_sdk::treasury::{TreasuryArgs, TreasuryError};
+use orbit_sdk::xdr::{self, AdapterArg, AssetArg, CrossLegArg, FallbackArg, RateArg};
+use orbit_sdk::OrbitError;
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Address, IntoVal, Symbol, TryFromVal, Val};
+use stellar_xdr::curr::{HostFunction, Limits, Operation, OperationBody, ReadXdr};
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TokenIndex, SCALAR_7};
+
+fn strkey(address: &Address) -> std::string::String {
+    address.to_string().to_string()
+}
+
+#[test]
+fn test_decode_contract_error() {
+    let fixture = create_fixture_with_data(false);
+    let ousd = &fixture.tokens[TokenIndex::OUSD].address;
+
+    let error = fixture
+        .treasury
+        .try_decrease_supply(ousd, &(100_000_000 * SCALAR_7))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        Ok(OrbitError::Treasury(TreasuryError::NotEnoughSupplyError)),
+        OrbitError::try_from(error)
+    );
+    assert_eq!(1504, OrbitError::try_from(error).unwrap().code());
+}
+
+#[test]
+fn test_parse_contract_error() {
+    assert_eq!(
+        Some(OrbitError::Treasury(TreasuryError::NotEnoughSupplyError)),
+        OrbitError::parse("HostError: Error(Contract, #1504)")
+    );
+    assert_eq!(
+        Some(OrbitError::BridgeOracle(BridgeOracleError::StalePriceError)),
+        OrbitError::parse("HostError: Error(Contract, #1402)")
+    );
+    assert_eq!(None, OrbitError::parse("HostError: Error(Contract, #9999)"));
+    assert_eq!(None, OrbitError::parse("HostError: Error(Auth, InvalidAction)"));
+}
+
+#[test]
+fn test_invocation_matches_contract_args() {
+    let fixture = create_fixture_with_data(false);
+    let e = &fixture.env;
+    let treasury = &fixture.treasury.address;
+    let ousd = &fixture.tokens[TokenIndex::OUSD].address;
+
+    let invocation = xdr::treasury::increase_supply(&strkey(treasury), &strkey(ousd), 100 * SCALAR_7).unwrap();
+    let args: soroban_sdk::Vec<Val> = TreasuryArgs::increase_supply(ousd, &(100 * SCALAR_7)).into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+    assert_eq!(
+        ScVal::try_from_val(e, &treasury.to_val()).unwrap(),
+        ScVal::Address(invocation.args.contract_address.clone())
+    );
+
+    let asset: Val = orbit_sdk::Asset::Other(Symbol::new(e, "EURO")).into_val(e);
+    let invocation = xdr::bridge_oracle::lastprice(&strkey(&fixture.bridge_oracle.address), AssetArg::Other("EURO")).unwrap();
+    assert_eq!(
+        ScVal::try_from_val(e, &asset).unwrap(),
+        invocation.args.args[0]
+    );
+
+    let oracle = &fixture.bridge_oracle.address;
+    let fallback = &fixture.oracle.address;
+    let invocation = xdr::bridge_oracle::set_fallbacks(
+        &strkey(oracle),
+        AssetArg::Stellar(&strkey(ousd)),
+        &[FallbackArg { oracle: &strkey(fallback), max_age: 600 }],
+    )
+    .unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::set_fallbacks(
+        &orbit_sdk::Asset::Stellar(ousd.clone()),
+        &soroban_sdk::vec![e, FallbackOracle { oracle: fallback.clone(), max_age: 600 }],
+    )
+    .into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+
+    let invocation = xdr::bridge_oracle::set_cross_rate(
+        &strkey(oracle),
+        AssetArg::Other("EURXLM"),
+        &[
+            CrossLegArg { asset: AssetArg::Other("EURO"), inverse: false },
+            CrossLegArg { asset: AssetArg::Stellar(&strkey(ousd)), inverse: true },
+        ],
+    )
+    .unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::set_cross_rate(
+        &orbit_sdk::Asset::Other(Symbol::new(e, "EURXLM")),
+        &soroban_sdk::vec![
+            e,
+            CrossLeg { asset: orbit_sdk::Asset::Other(Symbol::new(e, "EURO")), inverse: false },
+            CrossLeg { asset: orbit_sdk::Asset::Stellar(ousd.clone()), inverse: true },
+        ],
+    )
+    .into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+
+    let invocation = xdr::bridge_oracle::set_ratio(
+        &strkey(oracle),
+        AssetArg::Stellar(&strkey(ousd)),
+        RateArg::Contract(&strkey(fallback), "exchange_rate"),
+        7,
+    )
+    .unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::set_ratio(
+        &orbit_sdk::Asset::Stellar(ousd.clone()),
+        &Rate::Contract(fallback.clone(), Symbol::new(e, "exchange_rate")),
+        &7,
+    )
+    .into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+
+    let invocation = xdr::bridge_oracle::set_ratio(
+        &strkey(oracle),
+        AssetArg::Stellar(&strkey(ousd)),
+        RateArg::BlendBRate(&strkey(fallback), &strkey(ousd)),
+        12,
+    )
+    .unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::set_ratio(
+        &orbit_sdk::Asset::Stellar(ousd.clone()),
+        &Rate::BlendBRate(fallback.clone(), ousd.clone()),
+        &12,
+    )
+    .into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+
+    let invocation =
+        xdr::bridge_oracle::set_adapter(&strkey(oracle), &strkey(fallback), AdapterArg::Reflector).unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::set_adapter(fallback, &Adapter::Reflector).into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+
+    let invocation = xdr::bridge_oracle::poke(
+        &strkey(oracle),
+        &[AssetArg::Stellar(&strkey(ousd)), AssetArg::Other("EURO")],
+    )
+    .unwrap();
+    let args: soroban_sdk::Vec<Val> = BridgeOracleArgs::poke(&soroban_sdk::vec![
+        e,
+        orbit_sdk::Asset::Stellar(ousd.clone()),
+        orbit_sdk::Asset::Other(Symbol::new(e, "EURO")),
+    ])
+    .into_val(e);
+    let expected: Vec<ScVal> = args.iter().map(|arg| ScVal::try_from_val(e, &arg).unwrap()).collect();
+    assert_eq!(expected, invocation.args.args.to_vec());
+}
+
+#[test]
+fn test_invocation_xdr_roundtrip() {
+    let fixture = create_fixture_with_data(false);
+    let treasury = strkey(&fixture.treasury.address);
+    let ousd = strkey(&fixture.tokens[TokenIndex::OUSD].address);
+
+    let invocation = xdr::treasury::set_peg_band(&treasury, &ousd, 0_0500000).unwrap();
+    let operation = Operation::from_xdr_base64(invocation.to_xdr_base64().unwrap(), Limits::none()).unwrap();
+    match operation.body {
+        OperationBody::InvokeHostFunction(op) => {
+            assert_eq!(HostFunction::InvokeContract(invocation.args), op.host_function);
+            assert!(op.auth.is_empty());
+        }
+        _ => panic!("expected an invoke host function operation"),
+    }
+
+    assert!(xdr::treasury::set_peg_band(&ousd, "not a strkey", 0).is_err());
+}
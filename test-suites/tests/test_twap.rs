@@ -0,0 +1,179 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+use test_suites::assertions::assert_approx_eq_rel;
+use test_suites::create_fixture_with_data;
+use test_suites::test_fixture::{TestFixture, TokenIndex, SCALAR_7};
+
+const WINDOW: u64 = 60 * 60;
+
+/// Create a fixture with a BLND/USDC pair pricing BLND at 0.25 USDC
+fn create_fixture_with_pair<'a>() -> TestFixture<'a> {
+    let mut fixture = create_fixture_with_data(false);
+    fixture.create_pair(TokenIndex::BLND, TokenIndex::USDC, 1_000_000 * SCALAR_7, 250_000 * SCALAR_7);
+    fixture
+}
+
+/// Swap USDC for BLND in the pair, returning the BLND bought
+fn buy_blnd(fixture: &TestFixture, amount_in: i128) -> i128 {
+    swap(fixture, TokenIndex::USDC, TokenIndex::BLND, amount_in)
+}
+
+/// Swap BLND for USDC in the pair, returning the USDC bought
+fn sell_blnd(fixture: &TestFixture, amount_in: i128) -> i128 {
+    swap(fixture, TokenIndex::BLND, TokenIndex::USDC, amount_in)
+}
+
+fn swap(fixture: &TestFixture, token_in: TokenIndex, token_out: TokenIndex, amount_in: i128) -> i128 {
+    let pair = &fixture.pairs[0];
+    let token_out = &fixture.tokens[token_out].address;
+    let (reserve_0, reserve_1) = pair.get_reserves();
+    let (reserve_out, reserve_in) = if pair.token_0() == *token_out { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+    let amount_out = reserve_out * amount_in * 997 / (reserve_in * 1000 + amount_in * 997) - 1;
+
+    let trader = Address::generate(&fixture.env);
+    fixture.tokens[token_in].mint(&pair.address, &amount_in);
+    if pair.token_0() == *token_out {
+        pair.swap(&amount_out, &0, &trader);
+    } else {
+        pair.swap(&0, &amount_out, &trader);
+    }
+    amount_out
+}
+
+/// Set the TWAP source of BLND and observe the pair until the observations cover the window
+fn warm_up(fixture: &TestFixture, blnd: &Asset) {
+    fixture.bridge_oracle.set_twap(blnd, &fixture.pairs[0].address, &WINDOW, &(10_000 * SCALAR_7));
+    fixture.bridge_oracle.observe(blnd);
+    for _ in 0..4 {
+        fixture.jump(WINDOW / 4);
+        fixture.bridge_oracle.observe(blnd);
+    }
+}
+
+#[test]
+fn test_twap() {
+    let fixture = create_fixture_with_pair();
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    let pair = &fixture.pairs[0];
+
+    fixture.bridge_oracle.set_twap(&blnd, &pair.address, &WINDOW, &(10_000 * SCALAR_7));
+    assert_eq!(fixture.bridge_oracle.twap(&blnd).unwrap().window, WINDOW);
+
+    // the observations do not cover the window yet, and reading the price does not observe
+    assert!(fixture.bridge_oracle.lastprice(&blnd).is_none());
+    assert!(fixture.bridge_oracle.observe(&blnd).is_some());
+    for _ in 0..3 {
+        fixture.jump(WINDOW / 4);
+        assert!(fixture.bridge_oracle.lastprice(&blnd).is_none());
+        fixture.bridge_oracle.poke(&soroban_sdk::vec![&fixture.env, blnd.clone()]);
+    }
+    fixture.jump(WINDOW / 4);
+
+    // BLND is 0.25 USDC and USDC is 1 USD
+    let price = fixture.bridge_oracle.lastprice(&blnd).unwrap();
+    assert_eq!(price.price, 0_25_000_000_000_000);
+    assert_eq!(
+        fixture.bridge_oracle.lastprice_sources(&blnd),
+        soroban_sdk::vec![&fixture.env, pair.address.clone(), fixture.oracle.address.clone()]
+    );
+    fixture.bridge_oracle.observe(&blnd);
+
+    // the spot price moves to ~1 USDC, but each observation only steps 10% from the average
+    buy_blnd(&fixture, 250_000 * SCALAR_7);
+    let state = fixture.bridge_oracle.observe(&blnd).unwrap();
+    assert_eq!(state.spot, 0_27_500_000_000_000);
+    fixture.jump(WINDOW / 4);
+    let price = fixture.bridge_oracle.lastprice(&blnd).unwrap().price;
+    assert!(price > 0_25_000_000_000_000 && price < 0_27_500_000_000_000);
+
+    // a sustained move is followed over several windows
+    for _ in 0..48 {
+        fixture.jump(WINDOW / 4);
+        fixture.bridge_oracle.observe(&blnd);
+    }
+    let price = fixture.bridge_oracle.lastprice(&blnd).unwrap().price;
+    assert_approx_eq_rel(price, 1_00_000_000_000_000, 0_0100000);
+    let state = fixture.bridge_oracle.observe(&blnd).unwrap();
+    assert!(state.observations.len() <= 10);
+
+    // BLND is only listed while it has a TWAP source
+    assert!(fixture.bridge_oracle.assets().contains(&blnd));
+    fixture.bridge_oracle.remove_twap(&blnd);
+    assert!(!fixture.bridge_oracle.assets().contains(&blnd));
+}
+
+#[test]
+fn test_twap_swap_and_restore() {
+    let fixture = create_fixture_with_pair();
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    warm_up(&fixture, &blnd);
+    assert_eq!(fixture.bridge_oracle.lastprice(&blnd).unwrap().price, 0_25_000_000_000_000);
+
+    // within one ledger, the pair is skewed to ~1 USDC, observed and restored
+    let bought = buy_blnd(&fixture, 250_000 * SCALAR_7);
+    assert_eq!(fixture.bridge_oracle.lastprice(&blnd).unwrap().price, 0_25_000_000_000_000);
+    let state = fixture.bridge_oracle.observe(&blnd).unwrap();
+    assert_eq!(state.spot, 0_27_500_000_000_000);
+    sell_blnd(&fixture, bought);
+
+    // the skewed observation holds for a quarter of the window at most 10% above the average
+    fixture.jump(WINDOW / 4);
+    let price = fixture.bridge_oracle.lastprice(&blnd).unwrap().price;
+    assert_eq!(price, 0_25_625_000_000_000);
+    fixture.bridge_oracle.observe(&blnd);
+    for _ in 0..4 {
+        fixture.jump(WINDOW / 4);
+        fixture.bridge_oracle.observe(&blnd);
+    }
+    // the pair keeps the swap fees, so the restored spot price is slightly higher
+    assert_approx_eq_rel(fixture.bridge_oracle.lastprice(&blnd).unwrap().price, 0_25_000_000_000_000, 0_0050000);
+}
+
+#[test]
+fn test_twap_stale_observations() {
+    let fixture = create_fixture_with_pair();
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+    let pair = &fixture.pairs[0];
+
+    fixture.bridge_oracle.set_twap(&blnd, &pair.address, &WINDOW, &(10_000 * SCALAR_7));
+    fixture.bridge_oracle.observe(&blnd);
+
+    // a single observation of a skewed pair can't stand in for the whole window
+    fixture.jump(WINDOW / 8);
+    buy_blnd(&fixture, 250_000 * SCALAR_7);
+    fixture.bridge_oracle.observe(&blnd);
+    fixture.jump(WINDOW);
+    assert!(fixture.bridge_oracle.lastprice(&blnd).is_none());
+    let state = fixture.bridge_oracle.observe(&blnd).unwrap();
+    assert_eq!(state.observations.len(), 1);
+    assert_eq!(state.cumulative, 0);
+
+    // observations spaced more than a quarter of the window apart never cover it
+    for _ in 0..4 {
+        fixture.jump(WINDOW / 3);
+        fixture.bridge_oracle.observe(&blnd);
+        assert!(fixture.bridge_oracle.lastprice(&blnd).is_none());
+    }
+}
+
+#[test]
+fn test_twap_min_liquidity() {
+    let fixture = create_fixture_with_pair();
+    let blnd = Asset::Stellar(fixture.tokens[TokenIndex::BLND].address.clone());
+
+    fixture.bridge_oracle.set_twap(&blnd, &fixture.pairs[0].address, &WINDOW, &(500_000 * SCALAR_7));
+    fixture.jump(WINDOW);
+
+    assert!(fixture.bridge_oracle.observe(&blnd).is_none());
+    assert!(fixture.bridge_oracle.lastprice(&blnd).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1409)")]
+fn test_twap_asset_not_in_pair() {
+    let fixture = create_fixture_with_pair();
+    let xlm = Asset::Stellar(fixture.tokens[TokenIndex::XLM].address.clone());
+
+    fixture.bridge_oracle.set_twap(&xlm, &fixture.pairs[0].address, &WINDOW, &0);
+}
@@ -0,0 +1,79 @@
+use crate::constants::SCALAR_7;
+use crate::dependencies::backstop::Client as BackstopClient;
+use crate::dependencies::bridge_oracle::{Asset, Client as BridgeOracleClient};
+use crate::errors::TreasuryError;
+use crate::storage;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{panic_with_error, token, Address, Env};
+
+/// Fetch the supply the treasury can still add to the blend pool of a stablecoin
+///
+/// The cap is the backstop multiple of the token times the value of the backstop deposits
+/// of its blend pool, converted to units of the token at its bridge oracle price. Returns None
+/// if no backstop multiple is set for the token.
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+///
+/// ### Panics
+/// If the backstop or the oracle is not set
+/// If the token has no blend pool
+/// If the oracle has no price for the token or the backstop token
+pub fn headroom(e: &Env, token: &Address) -> Option<i128> {
+    let multiple = storage::get_backstop_multiple(e, token)?;
+    let backstop = storage::get_backstop(e).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::BackstopNotSetError);
+    });
+    let oracle = storage::get_oracle(e).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::OracleNotSetError);
+    });
+    let blend_pool = storage::get_blend_pool(e, token).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::BlendPoolNotFoundError);
+    });
+
+    let cap = backstop_value(e, &backstop, &oracle, &blend_pool, token).fixed_mul_floor(multiple, SCALAR_7).unwrap();
+    Some((cap - storage::get_total_supply(e, token)).max(0))
+}
+
+/// Require the supply increase to fit under the backstop cap of the stablecoin, if any
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `amount` - The amount the supply is increased by
+///
+/// ### Panics
+/// If the amount is larger than the headroom
+pub fn require_headroom(e: &Env, token: &Address, amount: i128) {
+    if let Some(headroom) = headroom(e, token) {
+        if amount > headroom {
+            panic_with_error!(e, TreasuryError::SupplyCapExceededError);
+        }
+    }
+}
+
+/// Fetch the value of the backstop deposits of a pool in units of the stablecoin, excluding
+/// queued withdrawals
+///
+/// The backstop token is priced by the bridge oracle, which values Comet LP shares at the fair
+/// value of the pool rather than its spot balances, so the cap can't be inflated by trading
+/// against the LP within a transaction.
+fn backstop_value(e: &Env, backstop: &Address, oracle: &Address, blend_pool: &Address, token: &Address) -> i128 {
+    let backstop_client = BackstopClient::new(e, backstop);
+    let pool_data = backstop_client.pool_data(blend_pool);
+    let backstop_token = backstop_client.backstop_token();
+
+    let bridge_oracle = BridgeOracleClient::new(e, oracle);
+    let share_price = bridge_oracle.lastprice(&Asset::Stellar(backstop_token.clone())).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::PriceNotFoundError);
+    });
+    let token_price = bridge_oracle.lastprice(&Asset::Stellar(token.clone())).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::PriceNotFoundError);
+    });
+
+    // both prices are in the decimals of the oracle, so only the token decimals differ
+    let share_scalar = 10i128.pow(token::TokenClient::new(e, &backstop_token).decimals());
+    let token_scalar = 10i128.pow(token::TokenClient::new(e, token).decimals());
+    let shares = pool_data.tokens.fixed_mul_floor(SCALAR_7 - pool_data.q4w_pct, SCALAR_7).unwrap();
+    let value = shares.fixed_mul_floor(share_price.price, token_price.price).unwrap();
+    value.fixed_mul_floor(token_scalar, share_scalar).unwrap()
+}
@@ -4,8 +4,12 @@ use crate::dependencies::pool_factory::{Client as PoolFactoryClient};
 use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, vec, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
 use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
 use soroban_fixed_point_math::{i128, FixedPoint};
-use crate::constants::{REQUEST_TYPE_SUPPLY, REQUEST_TYPE_WITHDRAW, SCALAR_12};
+use crate::constants::{REQUEST_TYPE_SUPPLY, REQUEST_TYPE_WITHDRAW, SCALAR_12, SCALAR_7};
 use crate::errors::TreasuryError;
+use crate::events::{self, EVENT_VERSION};
+use crate::peg;
+use crate::backstop;
+use crate::reserves::{self, ReserveReport};
 
 #[contract]
 pub struct TreasuryContract;
@@ -53,6 +57,70 @@ pub trait Treasury {
     /// If the caller is not the admin
     fn claim(e: Env, reserve_address: Address, to: Address) -> i128;
 
+    /// (Admin only) Set the bridge oracle used to check the peg before supply changes
+    ///
+    /// ### Arguments
+    /// * `oracle` - The Address of the bridge oracle
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_oracle(e: Env, oracle: Address);
+
+    /// (Admin only) Set the maximum deviation from peg allowed for supply changes of a stablecoin
+    ///
+    /// ### Arguments
+    /// * `token` - The Address for the token
+    /// * `band` - The deviation band, where 5% is 0_0500000
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// If the band is negative or not below 100%
+    fn set_peg_band(e: Env, token: Address, band: i128);
+
+    /// (Admin only) Set the Blend backstop used to cap the supply of stablecoins. The backstop
+    /// token must be priced by the bridge oracle
+    ///
+    /// ### Arguments
+    /// * `backstop` - The Address of the Blend backstop
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_backstop(e: Env, backstop: Address);
+
+    /// (Admin only) Cap the supply of a stablecoin to a multiple of its pool's backstop deposits
+    ///
+    /// ### Arguments
+    /// * `token` - The Address for the token
+    /// * `multiple` - The multiple of the backstop value, where 10x is 10_0000000
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// If the backstop or the oracle is not set
+    /// If the multiple is not positive
+    fn set_backstop_multiple(e: Env, token: Address, multiple: i128);
+
+    /// Fetch the supply that can still be added for a stablecoin under its backstop cap
+    ///
+    /// Returns None if the supply of the token is not capped
+    ///
+    /// ### Arguments
+    /// * `token` - The Address for the token
+    fn backstop_headroom(e: Env, token: Address) -> Option<i128>;
+
+    /// Fetch the reserve report of every stablecoin issued by the treasury
+    fn reserve_report(e: Env) -> Vec<ReserveReport>;
+
+    /// (Admin only) List stablecoins added before the treasury kept a list of its stablecoins,
+    /// so they are included in the reserve report
+    ///
+    /// ### Arguments
+    /// * `tokens` - The Addresses of the stablecoins
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// If a token has no blend pool
+    fn list_stablecoins(e: Env, tokens: Vec<Address>);
+
     /// (Admin only) Set a new address as the admin
     ///
     /// ### Arguments
@@ -77,7 +145,7 @@ impl TreasuryContract {
         storage::set_factory(&e, &factory);
         storage::set_admin(&e, &admin);
 
-        e.events().publish(("Treasury", Symbol::new(&e, "initialize")), (admin.clone(),));
+        events::Initialize { version: EVENT_VERSION, admin, factory }.publish(&e);
     }
 }
 
@@ -99,8 +167,11 @@ impl Treasury for TreasuryContract {
         }
 
         storage::set_blend_pool(&e, &token, &blend_pool);
+        let mut stablecoins = storage::get_stablecoins(&e);
+        stablecoins.push_back(token.clone());
+        storage::set_stablecoins(&e, &stablecoins);
 
-        e.events().publish(("Treasury", Symbol::new(&e, "add_stablecoin")), (token.clone(), blend_pool.clone()));
+        events::AddStablecoin { token, pool: blend_pool, version: EVENT_VERSION }.publish(&e);
     }
 
     fn increase_supply(e: Env, token: Address, amount: i128) {
@@ -112,6 +183,9 @@ impl Treasury for TreasuryContract {
             panic_with_error!(e, TreasuryError::InvalidAmount);
         }
 
+        peg::require_peg(&e, &token, true);
+        backstop::require_headroom(&e, &token, amount);
+
         token::StellarAssetClient::new(&e, &token).mint(&e.current_contract_address(), &amount);
 
         let blend = storage::get_blend_pool(&e, &token).unwrap_or_else(|| {
@@ -147,7 +221,7 @@ impl Treasury for TreasuryContract {
         total_supply += amount as i128;
         storage::set_total_supply(&e, &token.clone(), &total_supply);
 
-        e.events().publish(("Treasury", Symbol::new(&e, "increase_supply")), (token.clone(), amount.clone()));
+        events::IncreaseSupply { token, pool: blend, version: EVENT_VERSION, amount }.publish(&e);
     }
 
     fn decrease_supply(e: Env, token: Address, amount: i128) {
@@ -159,6 +233,8 @@ impl Treasury for TreasuryContract {
             panic_with_error!(e, TreasuryError::InvalidAmount);
         }
 
+        peg::require_peg(&e, &token, false);
+
         let token_client = token::TokenClient::new(&e, &token);
         let balance = token_client.balance(&e.current_contract_address());
 
@@ -184,7 +260,7 @@ impl Treasury for TreasuryContract {
         storage::set_total_supply(&e, &token.clone(), &total_supply);
 
         token::TokenClient::new(&e, &token).burn(&e.current_contract_address(), &amount);
-        e.events().publish(("Treasury", Symbol::new(&e, "decrease_supply")), (token.clone(), amount.clone()));
+        events::DecreaseSupply { token, pool: blend, version: EVENT_VERSION, amount }.publish(&e);
     }
 
     fn claim(e: Env, reserve_address: Address, to: Address) -> i128 {
@@ -218,10 +294,98 @@ impl Treasury for TreasuryContract {
             },
         ]);
 
-        e.events().publish(("Treasury", Symbol::new(&e, "claim")), (reserve_address.clone(), to.clone(), interest.clone()));
+        events::Claim { token: reserve_address, pool: blend_pool, version: EVENT_VERSION, to, amount: interest }.publish(&e);
         interest
     }
 
+    fn set_oracle(e: Env, oracle: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_oracle(&e, &oracle);
+
+        events::SetOracle { version: EVENT_VERSION, oracle }.publish(&e);
+    }
+
+    fn set_peg_band(e: Env, token: Address, band: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if !(0..SCALAR_7).contains(&band) {
+            panic_with_error!(e, TreasuryError::InvalidPegBandError);
+        }
+
+        storage::set_peg_band(&e, &token, &band);
+
+        events::SetPegBand { token, version: EVENT_VERSION, band }.publish(&e);
+    }
+
+    fn set_backstop(e: Env, backstop: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_backstop(&e, &backstop);
+
+        events::SetBackstop { version: EVENT_VERSION, backstop }.publish(&e);
+    }
+
+    fn set_backstop_multiple(e: Env, token: Address, multiple: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if storage::get_backstop(&e).is_none() {
+            panic_with_error!(e, TreasuryError::BackstopNotSetError);
+        }
+        if storage::get_oracle(&e).is_none() {
+            panic_with_error!(e, TreasuryError::OracleNotSetError);
+        }
+        if multiple <= 0 {
+            panic_with_error!(e, TreasuryError::InvalidAmount);
+        }
+
+        storage::set_backstop_multiple(&e, &token, &multiple);
+
+        events::SetBackstopMultiple { token, version: EVENT_VERSION, multiple }.publish(&e);
+    }
+
+    fn backstop_headroom(e: Env, token: Address) -> Option<i128> {
+        storage::extend_instance(&e);
+        backstop::headroom(&e, &token)
+    }
+
+    fn reserve_report(e: Env) -> Vec<ReserveReport> {
+        storage::extend_instance(&e);
+        let mut reports = Vec::new(&e);
+        for token in storage::get_stablecoins(&e).iter() {
+            reports.push_back(reserves::report(&e, &token));
+        }
+        reports
+    }
+
+    fn list_stablecoins(e: Env, tokens: Vec<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let mut stablecoins = storage::get_stablecoins(&e);
+        for token in tokens.iter() {
+            let blend_pool = storage::get_blend_pool(&e, &token).unwrap_or_else(|| {
+                panic_with_error!(e, TreasuryError::BlendPoolNotFoundError);
+            });
+            if stablecoins.contains(&token) {
+                continue;
+            }
+            stablecoins.push_back(token.clone());
+
+            events::ListStablecoin { token, pool: blend_pool, version: EVENT_VERSION }.publish(&e);
+        }
+        storage::set_stablecoins(&e, &stablecoins);
+    }
+
     fn set_admin(e: Env, new_admin: Address) {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
@@ -229,6 +393,6 @@ impl Treasury for TreasuryContract {
 
         storage::set_admin(&e, &new_admin);
 
-        e.events().publish(("Treasury", Symbol::new(&e, "set_admin")), new_admin.clone(),);
+        events::SetAdmin { version: EVENT_VERSION, admin: new_admin }.publish(&e);
     }
 }
@@ -0,0 +1,3 @@
+use soroban_sdk::contractimport;
+
+contractimport!(file = "../wasm/blend/backstop.wasm");
\ No newline at end of file
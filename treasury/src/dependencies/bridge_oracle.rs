@@ -0,0 +1,13 @@
+use soroban_sdk::{contractclient, Address, Env};
+pub use sep_40_oracle::{Asset, PriceData, PriceFeedClient};
+
+/// The subset of the bridge oracle interface used by the treasury
+#[allow(dead_code)]
+#[contractclient(name = "Client")]
+pub trait BridgeOracle {
+    fn decimals(env: Env) -> u32;
+
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+
+    fn stellar_oracle(env: Env) -> Address;
+}
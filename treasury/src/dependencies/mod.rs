@@ -1,2 +1,4 @@
+pub mod backstop;
+pub mod bridge_oracle;
 pub mod pool;
 pub mod pool_factory;
\ No newline at end of file
@@ -10,5 +10,11 @@ pub enum TreasuryError {
     BlendPoolNotFoundError = 1506,
     AlreadyAddedError = 1507,
     InvalidBlendPoolError = 1508,
-    NoInterestToClaim = 1509,    
+    NoInterestToClaim = 1509,
+    PegDeviationError = 1510,
+    PriceNotFoundError = 1511,
+    InvalidPegBandError = 1512,
+    SupplyCapExceededError = 1513,
+    BackstopNotSetError = 1514,
+    OracleNotSetError = 1515,
 }
\ No newline at end of file
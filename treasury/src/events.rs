@@ -0,0 +1,116 @@
+use soroban_sdk::{contractevent, Address};
+
+/// The version of the treasury event schema
+pub const EVENT_VERSION: u32 = 1;
+
+/// Emitted when the treasury is initialized
+#[contractevent(topics = ["Treasury", "initialize"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Initialize {
+    pub version: u32,
+    pub admin: Address,
+    pub factory: Address,
+}
+
+/// Emitted when a stablecoin is added to the treasury
+#[contractevent(topics = ["Treasury", "add_stablecoin"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddStablecoin {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+}
+
+/// Emitted when a stablecoin added before the treasury kept a list of its stablecoins is listed
+#[contractevent(topics = ["Treasury", "list_stablecoin"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListStablecoin {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+}
+
+/// Emitted when the supply of a stablecoin is minted into its blend pool
+#[contractevent(topics = ["Treasury", "increase_supply"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncreaseSupply {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+    pub amount: i128,
+}
+
+/// Emitted when the supply of a stablecoin is withdrawn from its blend pool and burned
+#[contractevent(topics = ["Treasury", "decrease_supply"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecreaseSupply {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+    pub amount: i128,
+}
+
+/// Emitted when the interest of a stablecoin is claimed
+#[contractevent(topics = ["Treasury", "claim"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub pool: Address,
+    pub version: u32,
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the bridge oracle used for peg checks is set
+#[contractevent(topics = ["Treasury", "set_oracle"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetOracle {
+    pub version: u32,
+    pub oracle: Address,
+}
+
+/// Emitted when the peg band of a stablecoin is set
+#[contractevent(topics = ["Treasury", "set_peg_band"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetPegBand {
+    #[topic]
+    pub token: Address,
+    pub version: u32,
+    pub band: i128,
+}
+
+/// Emitted when the Blend backstop used for supply caps is set
+#[contractevent(topics = ["Treasury", "set_backstop"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetBackstop {
+    pub version: u32,
+    pub backstop: Address,
+}
+
+/// Emitted when the backstop multiple of a stablecoin is set
+#[contractevent(topics = ["Treasury", "set_backstop_multiple"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetBackstopMultiple {
+    #[topic]
+    pub token: Address,
+    pub version: u32,
+    pub multiple: i128,
+}
+
+/// Emitted when the admin is changed
+#[contractevent(topics = ["Treasury", "set_admin"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetAdmin {
+    pub version: u32,
+    pub admin: Address,
+}
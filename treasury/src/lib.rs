@@ -1,4 +1,5 @@
 #![no_std]
+#![allow(clippy::upper_case_acronyms)]
 #[cfg(any(test, feature = "testutils"))]
 extern crate std;
 
@@ -6,5 +7,11 @@ mod storage;
 mod contract;
 mod constants;
 mod errors;
+pub mod events;
 mod dependencies;
+mod peg;
+mod backstop;
+mod reserves;
 pub use contract::*;
+pub use reserves::ReserveReport;
+pub use errors::TreasuryError;
@@ -0,0 +1,56 @@
+use crate::constants::SCALAR_7;
+use crate::dependencies::bridge_oracle::{Asset, Client as BridgeOracleClient, PriceFeedClient};
+use crate::errors::TreasuryError;
+use crate::storage;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Require the stablecoin to be close enough to its peg to change its supply
+///
+/// The peg price is read from the bridge oracle and the market price from the stellar oracle
+/// it wraps. Nothing is checked if the oracle or the band of the token is not configured.
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+/// * `is_expansion` - Whether the supply is increased or decreased
+///
+/// ### Panics
+/// If the market price is below peg by more than the band on expansion
+/// If the market price is above peg by more than the band on contraction
+pub fn require_peg(e: &Env, token: &Address, is_expansion: bool) {
+    let (oracle, band) = match (storage::get_oracle(e), storage::get_peg_band(e, token)) {
+        (Some(oracle), Some(band)) => (oracle, band),
+        _ => return,
+    };
+    let asset = Asset::Stellar(token.clone());
+
+    let bridge_oracle = BridgeOracleClient::new(e, &oracle);
+    let peg = bridge_oracle.lastprice(&asset).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::PriceNotFoundError);
+    });
+    let stellar_oracle = PriceFeedClient::new(e, &bridge_oracle.stellar_oracle());
+    let market = stellar_oracle.lastprice(&asset).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::PriceNotFoundError);
+    });
+    let market_price = to_decimals(market.price, stellar_oracle.decimals(), bridge_oracle.decimals());
+
+    if is_expansion {
+        let min_price = peg.price.fixed_mul_floor(SCALAR_7 - band, SCALAR_7).unwrap();
+        if market_price < min_price {
+            panic_with_error!(e, TreasuryError::PegDeviationError);
+        }
+    } else {
+        let max_price = peg.price.fixed_mul_ceil(SCALAR_7 + band, SCALAR_7).unwrap();
+        if market_price > max_price {
+            panic_with_error!(e, TreasuryError::PegDeviationError);
+        }
+    }
+}
+
+fn to_decimals(price: i128, from: u32, to: u32) -> i128 {
+    if from > to {
+        price / 10i128.pow(from - to)
+    } else {
+        price * 10i128.pow(to - from)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::constants::SCALAR_12;
+use crate::dependencies::pool::Client as PoolClient;
+use crate::errors::TreasuryError;
+use crate::storage;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+/// The reserves backing a stablecoin issued by the treasury
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveReport {
+    /// The stablecoin
+    pub token: Address,
+    /// The blend pool the stablecoin is supplied to
+    pub blend_pool: Address,
+    /// The net amount of the stablecoin minted by the treasury. Stellar asset contracts don't
+    /// export their total supply, so this is the minted figure tracked by the treasury itself
+    pub total_supply: i128,
+    /// The b-tokens the treasury holds in the blend pool
+    pub b_tokens: i128,
+    /// The b-token rate of the reserve, with 12 decimals
+    pub b_rate: i128,
+    /// The underlying value of the b-tokens
+    pub underlying: i128,
+    /// The interest accrued above the supply
+    pub interest: i128,
+    /// The shortfall of the underlying value below the supply
+    pub deficit: i128,
+}
+
+/// Build the reserve report of a stablecoin
+///
+/// ### Arguments
+/// * `token` - The Address of the stablecoin
+///
+/// ### Panics
+/// If the token has no blend pool
+pub fn report(e: &Env, token: &Address) -> ReserveReport {
+    let blend_pool = storage::get_blend_pool(e, token).unwrap_or_else(|| {
+        panic_with_error!(e, TreasuryError::BlendPoolNotFoundError);
+    });
+    let pool_client = PoolClient::new(e, &blend_pool);
+    let reserve = pool_client.get_reserve(token);
+    let position = pool_client.get_positions(&e.current_contract_address());
+
+    let b_tokens = position.supply.get(reserve.config.index).unwrap_or(0);
+    let underlying = b_tokens.fixed_mul_floor(reserve.data.b_rate, SCALAR_12).unwrap();
+    let total_supply = storage::get_total_supply(e, token);
+
+    ReserveReport {
+        token: token.clone(),
+        blend_pool,
+        total_supply,
+        b_tokens,
+        b_rate: reserve.data.b_rate,
+        underlying,
+        interest: (underlying - total_supply).max(0),
+        deficit: (total_supply - underlying).max(0),
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, contracttype, Env};
+use soroban_sdk::{Address, contracttype, Env, Vec};
 use soroban_sdk::unwrap::UnwrapOptimized;
 
 const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
@@ -14,6 +14,11 @@ pub enum TreasuryDataKey {
     BLENDPOOL(Address),
     FACTORY,
     TOTALSUPPLY(Address),
+    ORACLE,
+    PEGBAND(Address),
+    BACKSTOP,
+    BACKSTOPMULTIPLE(Address),
+    STABLECOINS,
 }
 
 pub fn extend_instance(e: &Env) {
@@ -48,6 +53,47 @@ pub fn set_factory(e: &Env, new_factory: &Address) {
         .set(&TreasuryDataKey::FACTORY, new_factory);
 }
 
+pub fn get_oracle(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&TreasuryDataKey::ORACLE)
+}
+
+pub fn set_oracle(e: &Env, new_oracle: &Address) {
+    e.storage()
+        .instance()
+        .set(&TreasuryDataKey::ORACLE, new_oracle);
+}
+
+pub fn get_backstop(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&TreasuryDataKey::BACKSTOP)
+}
+
+pub fn set_backstop(e: &Env, new_backstop: &Address) {
+    e.storage()
+        .instance()
+        .set(&TreasuryDataKey::BACKSTOP, new_backstop);
+}
+
+pub fn get_stablecoins(e: &Env) -> Vec<Address> {
+    // kept out of instance storage, which is loaded by every call
+    let key = TreasuryDataKey::STABLECOINS;
+    if let Some(result) = e.storage().persistent().get::<TreasuryDataKey, Vec<Address>>(&key) {
+        e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        result
+    } else {
+        Vec::new(e)
+    }
+}
+
+pub fn set_stablecoins(e: &Env, stablecoins: &Vec<Address>) {
+    let key = TreasuryDataKey::STABLECOINS;
+    e.storage().persistent().set::<TreasuryDataKey, Vec<Address>>(&key, stablecoins);
+    e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
 pub fn get_blend_pool(e: &Env, token_address: &Address) -> Option<Address> {
     let key = TreasuryDataKey::BLENDPOOL(token_address.clone());
     if let Some(result) = e.storage().persistent().get::<TreasuryDataKey, Address>(&key) {
@@ -80,4 +126,36 @@ pub fn set_total_supply(e: &Env, reserve_address: &Address, new_total_supply: &i
     let key = TreasuryDataKey::TOTALSUPPLY(reserve_address.clone());
     e.storage().persistent().set::<TreasuryDataKey, i128>(&key, new_total_supply);
     e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_peg_band(e: &Env, token_address: &Address) -> Option<i128> {
+    let key = TreasuryDataKey::PEGBAND(token_address.clone());
+    if let Some(result) = e.storage().persistent().get::<TreasuryDataKey, i128>(&key) {
+        e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        Some(result)
+    } else {
+        None
+    }
+}
+
+pub fn set_peg_band(e: &Env, token_address: &Address, band: &i128) {
+    let key = TreasuryDataKey::PEGBAND(token_address.clone());
+    e.storage().persistent().set::<TreasuryDataKey, i128>(&key, band);
+    e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+}
+
+pub fn get_backstop_multiple(e: &Env, token_address: &Address) -> Option<i128> {
+    let key = TreasuryDataKey::BACKSTOPMULTIPLE(token_address.clone());
+    if let Some(result) = e.storage().persistent().get::<TreasuryDataKey, i128>(&key) {
+        e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
+        Some(result)
+    } else {
+        None
+    }
+}
+
+pub fn set_backstop_multiple(e: &Env, token_address: &Address, multiple: &i128) {
+    let key = TreasuryDataKey::BACKSTOPMULTIPLE(token_address.clone());
+    e.storage().persistent().set::<TreasuryDataKey, i128>(&key, multiple);
+    e.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD_PERSISTANT, LEDGER_BUMP_PERSISTANT);
 }
\ No newline at end of file